@@ -0,0 +1,688 @@
+use crate::days_of_advent::common::budget::BudgetLimits;
+use crate::days_of_advent::common::cache::AnswerCache;
+use crate::days_of_advent::common::runner::RunOptions;
+use crate::days_of_advent::day01::{AddendsMode, Bound, EntryWidth, Witnesses};
+use crate::days_of_advent::day02::policies::positions::Positions;
+use std::time::Duration;
+
+pub const USAGE: &str = "\
+Usage:
+    advent-of-code-2020 [run] [DAY...] [--explain] [--cache | --cache-dir DIR | --refresh | --no-cache] [BUDGET]
+    advent-of-code-2020 run DAY --input FILE [BUDGET]
+    advent-of-code-2020 batch DAY [--dir DIR] [BUDGET]
+    advent-of-code-2020 serve [--addr HOST:PORT] [BUDGET]
+    advent-of-code-2020 addends [--input FILE] [--sum SUM] [--entries K] [--type TYPE] [--count | --unique | --nearest | --at-most | --at-least]
+    advent-of-code-2020 subset [--input FILE] [--sum SUM] [--min-entries A] [--max-entries B] [--type TYPE] [--all]
+    advent-of-code-2020 passwords [--input FILE] --rule RULE [--positions POSITIONS] [--explain]
+    advent-of-code-2020 normalize-passwords [--input FILE] [--output FILE]
+    advent-of-code-2020 audit-passwords [--input FILE]
+    advent-of-code-2020 generate-passwords [--rule RULE] [--policy POLICY] [--length N] [--alphabet CHARS] [--seed SEED] [--count N] [--valid K]
+    advent-of-code-2020 bench-parsers [--lines N] [--seed SEED]
+    advent-of-code-2020 new-day DAY --title TITLE [--description DESCRIPTION]
+
+Budget, limiting each solver:
+    --timeout SECONDS    give up once a solver has run this long
+    --max-steps STEPS    give up once a solver has taken this many steps
+
+Addends and subsets read entries as i64 unless --type gives one of i32, i64, u64 or i128.
+--nearest finds the entries whose sum is closest to SUM, --at-most and --at-least
+only from below or above it.
+
+Password rules combine the range and index policies of each line with and, or,
+xor, not and parentheses, such as \"range and not index\". A line may add
+policies its password must also pass, such as \"1-3 a; length 8-20; classes
+digit,upper; banned admin; regex ^[a-z]; run 2: password\". --explain lists the
+passwords that fail the rule, and why. --positions sets how index policies count,
+such as \"zero,grapheme,strict\": one or zero based, in char, grapheme or byte
+units, with out of range positions lenient, missing or strict.
+
+normalize-passwords rewrites each line of a password file in its canonical form,
+keeping lines it cannot read as they are. audit-passwords reports the spread of
+policies and password lengths, how many pass each policy, and how far failing
+passwords are from passing. generate-passwords writes COUNT lines, K of them
+passing the rule (by default range, with every line passing), each with POLICY
+such as \"1-3 a\" or a random one, and a LENGTH character password of ALPHABET,
+the same for the same SEED. bench-parsers times the regex and byte parsers of
+password lines over N generated lines (a million by default), the same for the
+same SEED; build with --release for numbers worth comparing";
+
+const DEFAULT_ADDRESS: &str = "127.0.0.1:2020";
+
+/// Served inputs come from anyone who can reach the port, so solvers get a
+/// timeout unless one is given
+const DEFAULT_SERVE_TIMEOUT_SECS: f64 = 10.0;
+
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    Run {
+        days: Vec<usize>,
+        options: RunOptions,
+    },
+    Batch {
+        day: usize,
+        dir: Option<String>,
+        budget: BudgetLimits,
+    },
+    Serve {
+        address: String,
+        budget: BudgetLimits,
+    },
+    Addends {
+        input: Option<String>,
+        /// Read as the same type as the entries, so kept as given
+        sum: String,
+        k: usize,
+        mode: AddendsMode,
+        width: EntryWidth,
+    },
+    Subset {
+        input: Option<String>,
+        sum: String,
+        min_size: usize,
+        max_size: Option<usize>,
+        witnesses: Witnesses,
+        width: EntryWidth,
+    },
+    Passwords {
+        input: Option<String>,
+        rule: String,
+        positions: Positions,
+        explain: bool,
+    },
+    NormalizePasswords {
+        input: Option<String>,
+        output: Option<String>,
+    },
+    AuditPasswords {
+        input: Option<String>,
+    },
+    GeneratePasswords {
+        rule: String,
+        policy: Option<String>,
+        length: usize,
+        alphabet: String,
+        seed: u64,
+        count: usize,
+        valid: usize,
+    },
+    BenchParsers {
+        lines: usize,
+        seed: u64,
+    },
+    NewDay {
+        day: usize,
+        title: String,
+        description: Option<String>,
+    },
+}
+
+pub fn parse_args(args: &[String]) -> Result<Command, String> {
+    match args.first().map(|a| a.as_str()) {
+        Some("new-day") => parse_new_day(&args[1..]),
+        Some("run") => parse_run(&args[1..]),
+        Some("batch") => parse_batch(&args[1..]),
+        Some("serve") => parse_serve(&args[1..]),
+        Some("addends") => parse_addends(&args[1..]),
+        Some("subset") => parse_subset(&args[1..]),
+        Some("passwords") => parse_passwords(&args[1..]),
+        Some("normalize-passwords") => parse_normalize_passwords(&args[1..]),
+        Some("audit-passwords") => parse_audit_passwords(&args[1..]),
+        Some("generate-passwords") => parse_generate_passwords(&args[1..]),
+        Some("bench-parsers") => parse_bench_parsers(&args[1..]),
+        _ => parse_run(args),
+    }
+}
+
+fn parse_run(args: &[String]) -> Result<Command, String> {
+    let mut days = vec![];
+    let mut options = RunOptions::default();
+
+    let mut itr = args.iter();
+    while let Some(arg) = itr.next() {
+        match arg.as_str() {
+            "--explain" => options.explain = true,
+            "--input" => options.input = Some(flag_value(arg, itr.next())?.into()),
+            "--cache" => options.cache = Some(AnswerCache::new(AnswerCache::default_dir())),
+            "--cache-dir" => options.cache = Some(AnswerCache::new(flag_value(arg, itr.next())?)),
+            "--refresh" => {
+                options.refresh = true;
+                options.cache.get_or_insert_with(|| AnswerCache::new(AnswerCache::default_dir()));
+            }
+            "--no-cache" => {
+                options.cache = None;
+                options.refresh = false;
+            }
+            "--timeout" | "--max-steps" => parse_budget_flag(&mut options.budget, arg, itr.next())?,
+            _ if arg.starts_with("--") => return Err(format!("Unknown option '{}'", arg)),
+            _ => days.push(parse_day(arg)?),
+        }
+    }
+
+    if options.input.is_some() && days.len() != 1 {
+        return Err("--input needs exactly one day".to_string());
+    }
+
+    Ok(Command::Run { days, options })
+}
+
+fn parse_batch(args: &[String]) -> Result<Command, String> {
+    let mut day = None;
+    let mut dir = None;
+    let mut budget = BudgetLimits::default();
+
+    let mut itr = args.iter();
+    while let Some(arg) = itr.next() {
+        match arg.as_str() {
+            "--dir" => dir = Some(flag_value(arg, itr.next())?),
+            "--timeout" | "--max-steps" => parse_budget_flag(&mut budget, arg, itr.next())?,
+            _ if day.is_none() => day = Some(parse_day(arg)?),
+            _ => return Err(format!("Unexpected argument '{}'", arg)),
+        }
+    }
+
+    Ok(Command::Batch {
+        day: day.ok_or("batch needs a day number")?,
+        dir,
+        budget,
+    })
+}
+
+fn parse_serve(args: &[String]) -> Result<Command, String> {
+    let mut address = DEFAULT_ADDRESS.to_string();
+    let mut budget = BudgetLimits {
+        timeout: Some(Duration::from_secs_f64(DEFAULT_SERVE_TIMEOUT_SECS)),
+        max_steps: None,
+    };
+
+    let mut itr = args.iter();
+    while let Some(arg) = itr.next() {
+        match arg.as_str() {
+            "--addr" => address = flag_value(arg, itr.next())?,
+            "--timeout" | "--max-steps" => parse_budget_flag(&mut budget, arg, itr.next())?,
+            _ => return Err(format!("Unexpected argument '{}'", arg)),
+        }
+    }
+
+    Ok(Command::Serve { address, budget })
+}
+
+fn parse_addends(args: &[String]) -> Result<Command, String> {
+    let mut input = None;
+    let mut sum = "2020".to_string();
+    let mut k = 2;
+    let mut mode = AddendsMode::List;
+    let mut width = EntryWidth::I64;
+
+    let mut itr = args.iter();
+    while let Some(arg) = itr.next() {
+        match arg.as_str() {
+            "--input" => input = Some(flag_value(arg, itr.next())?),
+            "--sum" => sum = flag_value(arg, itr.next())?,
+            "--entries" => {
+                let value = flag_value(arg, itr.next())?;
+                k = value.parse().map_err(|_| format!("'{}' is not a number of entries", value))?;
+            }
+            "--type" => width = flag_value(arg, itr.next())?.parse()?,
+            "--count" => mode = AddendsMode::Count,
+            "--unique" => mode = AddendsMode::Unique,
+            "--nearest" => mode = AddendsMode::Nearest(Bound::Any),
+            "--at-most" => mode = AddendsMode::Nearest(Bound::AtMost),
+            "--at-least" => mode = AddendsMode::Nearest(Bound::AtLeast),
+            _ => return Err(format!("Unexpected argument '{}'", arg)),
+        }
+    }
+
+    Ok(Command::Addends { input, sum, k, mode, width })
+}
+
+fn parse_subset(args: &[String]) -> Result<Command, String> {
+    let mut input = None;
+    let mut sum = "2020".to_string();
+    let mut min_size = 1;
+    let mut max_size = None;
+    let mut witnesses = Witnesses::One;
+    let mut width = EntryWidth::I64;
+
+    let mut itr = args.iter();
+    while let Some(arg) = itr.next() {
+        match arg.as_str() {
+            "--input" => input = Some(flag_value(arg, itr.next())?),
+            "--sum" => sum = flag_value(arg, itr.next())?,
+            "--min-entries" | "--max-entries" => {
+                let value = flag_value(arg, itr.next())?;
+                let size = value.parse().map_err(|_| format!("'{}' is not a number of entries", value))?;
+                match arg.as_str() {
+                    "--min-entries" => min_size = size,
+                    _ => max_size = Some(size),
+                }
+            }
+            "--type" => width = flag_value(arg, itr.next())?.parse()?,
+            "--all" => witnesses = Witnesses::All,
+            _ => return Err(format!("Unexpected argument '{}'", arg)),
+        }
+    }
+
+    Ok(Command::Subset { input, sum, min_size, max_size, witnesses, width })
+}
+
+fn parse_passwords(args: &[String]) -> Result<Command, String> {
+    let mut input = None;
+    let mut rule = None;
+    let mut positions = Positions::default();
+    let mut explain = false;
+
+    let mut itr = args.iter();
+    while let Some(arg) = itr.next() {
+        match arg.as_str() {
+            "--input" => input = Some(flag_value(arg, itr.next())?),
+            "--rule" => rule = Some(flag_value(arg, itr.next())?),
+            "--positions" => positions = flag_value(arg, itr.next())?.parse()?,
+            "--explain" => explain = true,
+            _ => return Err(format!("Unexpected argument '{}'", arg)),
+        }
+    }
+
+    Ok(Command::Passwords {
+        input,
+        rule: rule.ok_or("passwords needs a --rule")?,
+        positions,
+        explain,
+    })
+}
+
+fn parse_normalize_passwords(args: &[String]) -> Result<Command, String> {
+    let mut input = None;
+    let mut output = None;
+
+    let mut itr = args.iter();
+    while let Some(arg) = itr.next() {
+        match arg.as_str() {
+            "--input" => input = Some(flag_value(arg, itr.next())?),
+            "--output" => output = Some(flag_value(arg, itr.next())?),
+            _ => return Err(format!("Unexpected argument '{}'", arg)),
+        }
+    }
+
+    Ok(Command::NormalizePasswords { input, output })
+}
+
+fn parse_audit_passwords(args: &[String]) -> Result<Command, String> {
+    let mut input = None;
+
+    let mut itr = args.iter();
+    while let Some(arg) = itr.next() {
+        match arg.as_str() {
+            "--input" => input = Some(flag_value(arg, itr.next())?),
+            _ => return Err(format!("Unexpected argument '{}'", arg)),
+        }
+    }
+
+    Ok(Command::AuditPasswords { input })
+}
+
+fn parse_generate_passwords(args: &[String]) -> Result<Command, String> {
+    let mut rule = "range".to_string();
+    let mut policy = None;
+    let mut length = 10;
+    let mut alphabet = "abcdefghijklmnopqrstuvwxyz".to_string();
+    let mut seed = 2020;
+    let mut count = 1;
+    let mut valid = None;
+
+    let mut itr = args.iter();
+    while let Some(arg) = itr.next() {
+        match arg.as_str() {
+            "--rule" => rule = flag_value(arg, itr.next())?,
+            "--policy" => policy = Some(flag_value(arg, itr.next())?),
+            "--alphabet" => alphabet = flag_value(arg, itr.next())?,
+            "--seed" => {
+                let value = flag_value(arg, itr.next())?;
+                seed = value.parse().map_err(|_| format!("'{}' is not a seed", value))?;
+            }
+            "--length" | "--count" | "--valid" => {
+                let value = flag_value(arg, itr.next())?;
+                let number = value.parse().map_err(|_| format!("'{}' is not a number", value))?;
+                match arg.as_str() {
+                    "--length" => length = number,
+                    "--count" => count = number,
+                    _ => valid = Some(number),
+                }
+            }
+            _ => return Err(format!("Unexpected argument '{}'", arg)),
+        }
+    }
+
+    Ok(Command::GeneratePasswords { rule, policy, length, alphabet, seed, count, valid: valid.unwrap_or(count) })
+}
+
+fn parse_bench_parsers(args: &[String]) -> Result<Command, String> {
+    let mut lines = 1_000_000;
+    let mut seed = 2020;
+
+    let mut itr = args.iter();
+    while let Some(arg) = itr.next() {
+        match arg.as_str() {
+            "--lines" => {
+                let value = flag_value(arg, itr.next())?;
+                lines = value.parse().map_err(|_| format!("'{}' is not a number", value))?;
+            }
+            "--seed" => {
+                let value = flag_value(arg, itr.next())?;
+                seed = value.parse().map_err(|_| format!("'{}' is not a seed", value))?;
+            }
+            _ => return Err(format!("Unexpected argument '{}'", arg)),
+        }
+    }
+
+    Ok(Command::BenchParsers { lines, seed })
+}
+
+fn parse_new_day(args: &[String]) -> Result<Command, String> {
+    let mut day = None;
+    let mut title = None;
+    let mut description = None;
+
+    let mut itr = args.iter();
+    while let Some(arg) = itr.next() {
+        match arg.as_str() {
+            "--title" => title = Some(flag_value(arg, itr.next())?),
+            "--description" => description = Some(flag_value(arg, itr.next())?),
+            _ if day.is_none() => day = Some(parse_day(arg)?),
+            _ => return Err(format!("Unexpected argument '{}'", arg)),
+        }
+    }
+
+    Ok(Command::NewDay {
+        day: day.ok_or("new-day needs a day number")?,
+        title: title.ok_or("new-day needs a --title")?,
+        description,
+    })
+}
+
+fn parse_budget_flag(budget: &mut BudgetLimits, flag: &str, value: Option<&String>) -> Result<(), String> {
+    let value = flag_value(flag, value)?;
+    match flag {
+        "--timeout" => {
+            let seconds = value
+                .parse::<f64>()
+                .ok()
+                .filter(|s| s.is_finite() && *s > 0.0)
+                .ok_or(format!("'{}' is not a number of seconds", value))?;
+            budget.timeout = Some(Duration::from_secs_f64(seconds));
+        }
+        _ => {
+            let steps = value
+                .parse::<u64>()
+                .map_err(|_| format!("'{}' is not a number of steps", value))?;
+            budget.max_steps = Some(steps);
+        }
+    }
+    Ok(())
+}
+
+fn flag_value(flag: &str, value: Option<&String>) -> Result<String, String> {
+    value
+        .cloned()
+        .ok_or(format!("{} needs a value", flag))
+}
+
+fn parse_day(arg: &str) -> Result<usize, String> {
+    arg.parse::<usize>()
+        .map_err(|_| format!("'{}' is not a day number", arg))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(s: &str) -> Vec<String> {
+        s.split_whitespace().map(|a| a.to_string()).collect()
+    }
+
+    #[test]
+    fn no_arguments_runs_every_day() {
+        let solution = Command::Run {
+            days: vec![],
+            options: RunOptions::default(),
+        };
+
+        assert_eq!(parse_args(&[]).unwrap(), solution);
+    }
+
+    #[test]
+    fn run_selected_days() {
+        let solution = Command::Run {
+            days: vec![1, 7],
+            options: RunOptions::default(),
+        };
+
+        assert_eq!(parse_args(&args("run 1 7")).unwrap(), solution);
+        assert!(parse_args(&args("run seven")).is_err());
+    }
+
+    #[test]
+    fn run_with_explanations() {
+        let solution = Command::Run {
+            days: vec![3],
+            options: RunOptions { explain: true, ..Default::default() },
+        };
+
+        assert_eq!(parse_args(&args("3 --explain")).unwrap(), solution);
+        assert_eq!(parse_args(&args("run --explain 3")).unwrap(), solution);
+    }
+
+    #[test]
+    fn run_one_day_from_input_file() {
+        let solution = Command::Run {
+            days: vec![2],
+            options: RunOptions { input: Some("big.txt".into()), ..Default::default() },
+        };
+
+        assert_eq!(parse_args(&args("run 2 --input big.txt")).unwrap(), solution);
+        assert!(parse_args(&args("run --input big.txt")).is_err());
+        assert!(parse_args(&args("run 2 4 --input big.txt")).is_err());
+    }
+
+    #[test]
+    fn run_with_answer_cache() {
+        let parsed = |s: &str| match parse_args(&args(s)).unwrap() {
+            Command::Run { options, .. } => options,
+            _ => panic!("Expected a run command"),
+        };
+
+        assert_eq!(parsed("1").cache, None);
+        assert_eq!(parsed("1 --cache").cache, Some(AnswerCache::new(AnswerCache::default_dir())));
+        assert_eq!(parsed("1 --cache-dir answers").cache, Some(AnswerCache::new("answers")));
+        assert!(parsed("1 --refresh").refresh);
+        assert!(parsed("1 --refresh").cache.is_some());
+        assert_eq!(parsed("1 --cache --no-cache").cache, None);
+    }
+
+    #[test]
+    fn batch_with_directory() {
+        let solution = Command::Batch {
+            day: 2,
+            dir: Some("team/day02".to_string()),
+            budget: BudgetLimits::default(),
+        };
+
+        assert_eq!(parse_args(&args("batch 2 --dir team/day02")).unwrap(), solution);
+        assert_eq!(
+            parse_args(&args("batch 2")).unwrap(),
+            Command::Batch { day: 2, dir: None, budget: BudgetLimits::default() }
+        );
+        assert!(parse_args(&args("batch")).is_err());
+    }
+
+    #[test]
+    fn serve_on_address() {
+        let default_budget = BudgetLimits { timeout: Some(Duration::from_secs(10)), max_steps: None };
+
+        assert_eq!(
+            parse_args(&args("serve")).unwrap(),
+            Command::Serve { address: "127.0.0.1:2020".to_string(), budget: default_budget }
+        );
+        assert_eq!(
+            parse_args(&args("serve --addr 0.0.0.0:8080")).unwrap(),
+            Command::Serve { address: "0.0.0.0:8080".to_string(), budget: default_budget }
+        );
+    }
+
+    #[test]
+    fn budget_flags() {
+        let budget = |s: &str| match parse_args(&args(s)).unwrap() {
+            Command::Run { options, .. } => options.budget,
+            Command::Batch { budget, .. } | Command::Serve { budget, .. } => budget,
+            _ => panic!("Expected a command with a budget"),
+        };
+
+        assert_eq!(budget("7"), BudgetLimits::default());
+        assert_eq!(budget("7 --timeout 1.5").timeout, Some(Duration::from_millis(1500)));
+        assert_eq!(budget("batch 8 --max-steps 1000").max_steps, Some(1000));
+        assert_eq!(budget("serve --timeout 2").timeout, Some(Duration::from_secs(2)));
+        assert!(parse_args(&args("7 --timeout soon")).is_err());
+        assert!(parse_args(&args("7 --timeout -1")).is_err());
+        assert!(parse_args(&args("7 --max-steps")).is_err());
+    }
+
+    #[test]
+    fn addends_modes() {
+        assert_eq!(
+            parse_args(&args("addends")).unwrap(),
+            Command::Addends {
+                input: None,
+                sum: "2020".to_string(),
+                k: 2,
+                mode: AddendsMode::List,
+                width: EntryWidth::I64
+            }
+        );
+        assert_eq!(
+            parse_args(&args("addends --input report.txt --entries 3 --sum 100 --type u64 --unique")).unwrap(),
+            Command::Addends {
+                input: Some("report.txt".to_string()),
+                sum: "100".to_string(),
+                k: 3,
+                mode: AddendsMode::Unique,
+                width: EntryWidth::U64
+            }
+        );
+        assert!(parse_args(&args("addends --count --entries three")).is_err());
+        assert!(parse_args(&args("addends --type u8")).is_err());
+        assert!(matches!(
+            parse_args(&args("addends --sum 2000 --at-most")).unwrap(),
+            Command::Addends { mode: AddendsMode::Nearest(Bound::AtMost), .. }
+        ));
+    }
+
+    #[test]
+    fn subset_sizes() {
+        assert_eq!(
+            parse_args(&args("subset --sum 100 --min-entries 2 --max-entries 5 --all")).unwrap(),
+            Command::Subset {
+                input: None,
+                sum: "100".to_string(),
+                min_size: 2,
+                max_size: Some(5),
+                witnesses: Witnesses::All,
+                width: EntryWidth::I64
+            }
+        );
+        assert!(parse_args(&args("subset --max-entries many")).is_err());
+    }
+
+    #[test]
+    fn passwords_rule() {
+        let command = vec!["passwords".to_string(), "--rule".to_string(), "range and not index".to_string()];
+
+        assert_eq!(
+            parse_args(&command).unwrap(),
+            Command::Passwords { input: None, rule: "range and not index".to_string(), positions: Positions::default(), explain: false }
+        );
+        assert_eq!(
+            parse_args(&args("passwords --rule index --positions zero,byte --explain")).unwrap(),
+            Command::Passwords {
+                input: None,
+                rule: "index".to_string(),
+                positions: "zero,byte".parse().unwrap(),
+                explain: true
+            }
+        );
+        assert!(parse_args(&args("passwords")).is_err());
+    }
+
+    #[test]
+    fn normalize_passwords_files() {
+        assert_eq!(
+            parse_args(&args("normalize-passwords --input in.txt --output out.txt")).unwrap(),
+            Command::NormalizePasswords { input: Some("in.txt".to_string()), output: Some("out.txt".to_string()) }
+        );
+        assert!(parse_args(&args("normalize-passwords --rule range")).is_err());
+        assert_eq!(parse_args(&args("audit-passwords")).unwrap(), Command::AuditPasswords { input: None });
+    }
+
+    #[test]
+    fn generate_passwords_defaults() {
+        assert_eq!(
+            parse_args(&args("generate-passwords --count 5")).unwrap(),
+            Command::GeneratePasswords {
+                rule: "range".to_string(),
+                policy: None,
+                length: 10,
+                alphabet: "abcdefghijklmnopqrstuvwxyz".to_string(),
+                seed: 2020,
+                count: 5,
+                valid: 5,
+            }
+        );
+        let mut command = args("generate-passwords --rule index --length 6 --alphabet abc --seed 7");
+        command.extend(args("--count 4 --valid 1"));
+        command.extend(vec!["--policy".to_string(), "1-3 a".to_string()]);
+        assert_eq!(
+            parse_args(&command).unwrap(),
+            Command::GeneratePasswords {
+                rule: "index".to_string(),
+                policy: Some("1-3 a".to_string()),
+                length: 6,
+                alphabet: "abc".to_string(),
+                seed: 7,
+                count: 4,
+                valid: 1,
+            }
+        );
+        assert!(parse_args(&args("generate-passwords --seed x")).is_err());
+    }
+
+    #[test]
+    fn bench_parsers_flags() {
+        assert_eq!(
+            parse_args(&args("bench-parsers")).unwrap(),
+            Command::BenchParsers { lines: 1_000_000, seed: 2020 }
+        );
+        assert_eq!(
+            parse_args(&args("bench-parsers --lines 500 --seed 7")).unwrap(),
+            Command::BenchParsers { lines: 500, seed: 7 }
+        );
+        assert!(parse_args(&args("bench-parsers --lines many")).is_err());
+    }
+
+    #[test]
+    fn new_day_with_title() {
+        let mut input = args("new-day 9 --title");
+        input.push("Encoding Error".to_string());
+
+        let solution = Command::NewDay {
+            day: 9,
+            title: "Encoding Error".to_string(),
+            description: None,
+        };
+
+        assert_eq!(parse_args(&input).unwrap(), solution);
+    }
+
+    #[test]
+    fn new_day_needs_title() {
+        assert!(parse_args(&args("new-day 9")).is_err());
+        assert!(parse_args(&args("new-day --title")).is_err());
+    }
+}
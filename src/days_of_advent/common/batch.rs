@@ -0,0 +1,160 @@
+use super::budget::BudgetLimits;
+use super::io::NamedInput;
+use super::puzzle::Puzzle;
+use super::runner::{self, RunOptions};
+
+/// The answers a day gave for one of a batch of inputs
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BatchResult {
+    pub name: String,
+    pub answers: Result<Vec<String>, String>,
+    pub expected: Option<Vec<String>>,
+}
+
+impl BatchResult {
+    /// The parts, counting from 1, whose answer differs from the expected one.
+    /// Blank expected answers are not checked
+    pub fn mismatched_parts(&self) -> Vec<usize> {
+        match (&self.answers, &self.expected) {
+            (Ok(answers), Some(expected)) => expected
+                .iter()
+                .enumerate()
+                .filter(|(_, e)| !e.is_empty())
+                .filter(|(idx, e)| answers.get(*idx) != Some(e))
+                .map(|(idx, _)| idx + 1)
+                .collect(),
+            _ => vec![],
+        }
+    }
+
+    pub fn is_ok(&self) -> bool {
+        self.answers.is_ok() && self.mismatched_parts().is_empty()
+    }
+
+    pub fn status(&self) -> String {
+        let mismatched_parts = self.mismatched_parts();
+        match (&self.answers, &self.expected) {
+            (Err(e), _) => format!("error: {}", e),
+            (Ok(_), None) => "unchecked".to_string(),
+            (Ok(_), Some(expected)) if !mismatched_parts.is_empty() => mismatched_parts
+                .iter()
+                .map(|part| format!("part {} expected {}", part, expected[part - 1]))
+                .collect::<Vec<String>>()
+                .join(", "),
+            (Ok(_), Some(_)) => "ok".to_string(),
+        }
+    }
+}
+
+/// Solve the day for each input in turn, each with its own `budget`. A
+/// solver that panics or runs out of budget on one input is recorded as an
+/// error for that input, and the batch carries on
+pub fn run_batch(puzzle: &dyn Puzzle, inputs: Vec<NamedInput>, budget: BudgetLimits) -> Vec<BatchResult> {
+    let options = RunOptions { budget, ..Default::default() };
+    inputs
+        .into_iter()
+        .map(|input| BatchResult {
+            answers: runner::solve_guarded(puzzle, &input.data, &options)
+                .map(|answers| answers.into_iter().map(|a| a.value).collect()),
+            name: input.name,
+            expected: input.expected,
+        })
+        .collect()
+}
+
+/// Lay out a batch as a table of inputs against the answers for each part
+pub fn render_batch_table(results: &[BatchResult]) -> String {
+    let num_parts = results
+        .iter()
+        .filter_map(|r| r.answers.as_ref().ok().map(|a| a.len()))
+        .max()
+        .unwrap_or(1);
+
+    let mut rows: Vec<Vec<String>> = vec![];
+    let mut header = vec!["input".to_string()];
+    header.extend((1..=num_parts).map(|p| format!("part {}", p)));
+    header.push("status".to_string());
+    rows.push(header);
+
+    for result in results {
+        let mut row = vec![result.name.clone()];
+        let answers = result.answers.as_ref().map(|a| a.as_slice()).unwrap_or_default();
+        row.extend((0..num_parts).map(|idx| answers.get(idx).cloned().unwrap_or_else(|| "-".to_string())));
+        row.push(result.status());
+        rows.push(row);
+    }
+
+    let widths: Vec<usize> = (0..rows[0].len())
+        .map(|col| rows.iter().map(|r| r[col].chars().count()).max().unwrap())
+        .collect();
+
+    let format_row = |row: &Vec<String>| {
+        row.iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{:width$}", cell, width = width))
+            .collect::<Vec<String>>()
+            .join(" | ")
+            .trim_end()
+            .to_string()
+    };
+
+    let mut lines = vec![format_row(&rows[0])];
+    lines.push(widths.iter().map(|w| "-".repeat(*w)).collect::<Vec<String>>().join("-+-"));
+    lines.extend(rows[1..].iter().map(format_row));
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(name: &str, answers: Result<Vec<&str>, &str>, expected: Option<Vec<&str>>) -> BatchResult {
+        let to_strings = |v: Vec<&str>| v.into_iter().map(|s| s.to_string()).collect::<Vec<String>>();
+        BatchResult {
+            name: name.to_string(),
+            answers: answers.map(to_strings).map_err(|e| e.to_string()),
+            expected: expected.map(to_strings),
+        }
+    }
+
+    #[test]
+    fn status_compares_expected_answers() {
+        assert_eq!(result("a", Ok(vec!["1", "2"]), Some(vec!["1", "2"])).status(), "ok");
+        assert_eq!(result("a", Ok(vec!["1", "2"]), Some(vec!["1", ""])).status(), "ok");
+        assert_eq!(result("a", Ok(vec!["1", "2"]), None).status(), "unchecked");
+        assert_eq!(result("a", Ok(vec!["1", "2"]), Some(vec!["1", "3"])).status(), "part 2 expected 3");
+        assert_eq!(result("a", Err("bad input"), None).status(), "error: bad input");
+        assert!(!result("a", Err("bad input"), None).is_ok());
+    }
+
+    #[test]
+    fn render_batch_table_aligns_columns() {
+        let results = vec![
+            result("alice", Ok(vec!["1", "200"]), Some(vec!["1", "200"])),
+            result("bob", Ok(vec!["30", "4"]), None),
+        ];
+
+        let table = render_batch_table(&results);
+
+        let expected = "\
+input | part 1 | part 2 | status
+------+--------+--------+----------
+alice | 1      | 200    | ok
+bob   | 30     | 4      | unchecked";
+        assert_eq!(table, expected);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn batch_results_round_trip_through_json() {
+        let results = vec![
+            result("alice", Ok(vec!["1", "200"]), Some(vec!["1", "200"])),
+            result("bob", Err("bad input"), None),
+        ];
+
+        let json = serde_json::to_string(&results).unwrap();
+
+        assert_eq!(serde_json::from_str::<Vec<BatchResult>>(&json).unwrap(), results);
+    }
+}
@@ -0,0 +1,130 @@
+use std::cell::Cell;
+use std::time::{Duration, Instant};
+
+/// How long a solver may run and how many steps it may take. `None` means
+/// no limit
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct BudgetLimits {
+    pub timeout: Option<Duration>,
+    pub max_steps: Option<u64>,
+}
+
+impl BudgetLimits {
+    /// Start spending a budget with these limits, timed from now
+    pub fn start(&self) -> Budget {
+        Budget {
+            limits: *self,
+            started: Instant::now(),
+            steps: Cell::new(0),
+        }
+    }
+}
+
+/// A solver gave up because it ran out of budget
+#[derive(Debug, PartialEq)]
+pub enum BudgetExceeded {
+    Timeout(Duration),
+    Steps(u64),
+}
+
+impl std::fmt::Display for BudgetExceeded {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            BudgetExceeded::Timeout(timeout) => write!(f, "Budget exceeded: ran longer than {:?}", timeout),
+            BudgetExceeded::Steps(max_steps) => write!(f, "Budget exceeded: took more than {} steps", max_steps),
+        }
+    }
+}
+
+impl From<BudgetExceeded> for String {
+    fn from(e: BudgetExceeded) -> Self {
+        e.to_string()
+    }
+}
+
+/// The budget a solver is spending. Solvers whose loops or recursion depend
+/// on the input call `step` as they go, and stop when it fails
+#[derive(Debug)]
+pub struct Budget {
+    limits: BudgetLimits,
+    started: Instant,
+    steps: Cell<u64>,
+}
+
+impl Budget {
+    pub fn unlimited() -> Self {
+        BudgetLimits::default().start()
+    }
+
+    /// Count one step of work and check that the budget still holds
+    pub fn step(&self) -> Result<(), BudgetExceeded> {
+        let steps = self.steps.get() + 1;
+        self.steps.set(steps);
+
+        match self.limits.max_steps {
+            Some(max_steps) if steps > max_steps => Err(BudgetExceeded::Steps(max_steps)),
+            _ => self.check_time(),
+        }
+    }
+
+    /// Check the clock without counting a step
+    pub fn check_time(&self) -> Result<(), BudgetExceeded> {
+        match self.limits.timeout {
+            Some(timeout) if self.started.elapsed() > timeout => Err(BudgetExceeded::Timeout(timeout)),
+            _ => Ok(()),
+        }
+    }
+
+    /// Spend a step on each line or record of a streamed input, so that
+    /// streaming solvers stop once the budget runs out
+    pub fn meter<'b, T: 'b>(
+        &'b self,
+        items: impl Iterator<Item = Result<T, String>> + 'b,
+    ) -> impl Iterator<Item = Result<T, String>> + 'b {
+        items.map(move |item| {
+            self.step()?;
+            item
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unlimited_budget_never_runs_out() {
+        let budget = Budget::unlimited();
+
+        assert!((0..10_000).all(|_| budget.step().is_ok()));
+        assert_eq!(budget.check_time(), Ok(()));
+    }
+
+    #[test]
+    fn step_cap_is_enforced() {
+        let budget = BudgetLimits { max_steps: Some(3), ..Default::default() }.start();
+
+        assert!((0..3).all(|_| budget.step().is_ok()));
+        assert_eq!(budget.step(), Err(BudgetExceeded::Steps(3)));
+        assert_eq!(String::from(BudgetExceeded::Steps(3)), "Budget exceeded: took more than 3 steps");
+    }
+
+    #[test]
+    fn meter_stops_streams_when_budget_runs_out() {
+        let budget = BudgetLimits { max_steps: Some(2), ..Default::default() }.start();
+        let lines = vec![Ok("a"), Ok("b"), Ok("c")];
+
+        let metered: Vec<Result<&str, String>> = budget.meter(lines.into_iter()).collect();
+
+        assert_eq!(metered[..2], [Ok("a"), Ok("b")]);
+        assert_eq!(metered[2], Err("Budget exceeded: took more than 2 steps".to_string()));
+    }
+
+    #[test]
+    fn timeout_is_enforced() {
+        let budget = BudgetLimits { timeout: Some(Duration::from_millis(1)), ..Default::default() }.start();
+        std::thread::sleep(Duration::from_millis(5));
+
+        assert_eq!(budget.step(), Err(BudgetExceeded::Timeout(Duration::from_millis(1))));
+    }
+}
@@ -0,0 +1,243 @@
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use super::puzzle::{Answer, Puzzle};
+
+/// A directory of previously computed answers, keyed by day, part, a hash of
+/// the input, and the solver's version
+#[derive(Debug, PartialEq)]
+pub struct AnswerCache {
+    dir: PathBuf,
+}
+
+impl AnswerCache {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        AnswerCache { dir: dir.into() }
+    }
+
+    /// The cache lives under `target/` so that `cargo clean` clears it
+    pub fn default_dir() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("target").join("answer-cache")
+    }
+
+    /// Look up every part's answer, or `None` unless an entry was stored
+    /// whole for this very input. Entries are found by a 64-bit hash, so a
+    /// different input sharing it reads as a miss
+    pub fn get_all(&self, puzzle: &dyn Puzzle, input: &str) -> Option<Vec<Answer>> {
+        let data = std::fs::read_to_string(self.answers_path(puzzle, input)).ok()?;
+        decode_answers(&data, input)
+    }
+
+    /// Store every part's answer at once. The entry is written aside and
+    /// renamed into place, so readers see all of it or none of it
+    pub fn put_all(&self, puzzle: &dyn Puzzle, input: &str, answers: &[Answer]) -> Result<(), String> {
+        static WRITES: AtomicUsize = AtomicUsize::new(0);
+
+        let entry_dir = self.entry_dir(puzzle, input);
+        std::fs::create_dir_all(&entry_dir).map_err(|e| e.to_string())?;
+
+        let write = WRITES.fetch_add(1, Ordering::Relaxed);
+        let temp_path = entry_dir.join(format!("answers.{}.{}.tmp", std::process::id(), write));
+        std::fs::write(&temp_path, encode_answers(input, answers)).map_err(|e| e.to_string())?;
+        std::fs::rename(&temp_path, self.answers_path(puzzle, input)).map_err(|e| {
+            let _ = std::fs::remove_file(&temp_path);
+            e.to_string()
+        })
+    }
+
+    fn entry_dir(&self, puzzle: &dyn Puzzle, input: &str) -> PathBuf {
+        let version: String = puzzle
+            .version()
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() || c == '.' { c } else { '_' })
+            .collect();
+
+        self.dir
+            .join(puzzle.name())
+            .join(format!("v{}-{:016x}", version, hash_input(input.as_bytes())))
+    }
+
+    fn answers_path(&self, puzzle: &dyn Puzzle, input: &str) -> PathBuf {
+        self.entry_dir(puzzle, input).join("answers")
+    }
+}
+
+/// Each field is its length in bytes on one line, then the field itself and a
+/// newline, so fields may hold newlines of their own. The fields are the
+/// input's length and digest, the number of answers, then for each its value,
+/// summary, number of explanation lines and those lines
+fn encode_answers(input: &str, answers: &[Answer]) -> String {
+    let mut data = String::new();
+    let mut push_field = |field: &str| {
+        data.push_str(&format!("{}\n{}\n", field.len(), field));
+    };
+
+    push_field(&input.len().to_string());
+    push_field(&format!("{:032x}", digest_input(input.as_bytes())));
+    push_field(&answers.len().to_string());
+    for answer in answers {
+        push_field(&answer.value);
+        push_field(&answer.summary);
+        push_field(&answer.explanation.len().to_string());
+        for line in &answer.explanation {
+            push_field(line);
+        }
+    }
+    data
+}
+
+/// The answers `encode_answers` wrote for `input`, or `None` if `data` is not
+/// exactly that
+fn decode_answers(data: &str, input: &str) -> Option<Vec<Answer>> {
+    let mut rest = data;
+    let mut next_field = || {
+        let (length, after) = rest.split_once('\n')?;
+        let length: usize = length.parse().ok()?;
+        let field = after.get(..length)?;
+        rest = after[length..].strip_prefix('\n')?;
+        Some(field.to_string())
+    };
+
+    if next_field()? != input.len().to_string()
+        || next_field()? != format!("{:032x}", digest_input(input.as_bytes()))
+    {
+        return None;
+    }
+
+    let num_answers: usize = next_field()?.parse().ok()?;
+    let mut answers = vec![];
+    for _ in 0..num_answers {
+        let value = next_field()?;
+        let summary = next_field()?;
+        let num_lines: usize = next_field()?.parse().ok()?;
+        let explanation = (0..num_lines).map(|_| next_field()).collect::<Option<_>>()?;
+        answers.push(Answer { value, summary, explanation });
+    }
+
+    match rest.is_empty() && !answers.is_empty() {
+        true => Some(answers),
+        false => None,
+    }
+}
+
+/// 64-bit FNV-1a, which unlike the standard library's hasher is stable
+/// between builds
+pub fn hash_input(bytes: &[u8]) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    bytes
+        .iter()
+        .fold(OFFSET_BASIS, |hash, byte| (hash ^ *byte as u64).wrapping_mul(PRIME))
+}
+
+/// 128-bit FNV-1a, stored in each entry so that inputs whose 64-bit hashes
+/// collide are still told apart
+fn digest_input(bytes: &[u8]) -> u128 {
+    const OFFSET_BASIS: u128 = 0x6c62_272e_07bb_0142_62b8_2175_6295_c58d;
+    const PRIME: u128 = 0x0000_0000_0100_0000_0000_0000_0000_013b;
+
+    bytes
+        .iter()
+        .fold(OFFSET_BASIS, |hash, byte| (hash ^ *byte as u128).wrapping_mul(PRIME))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Versioned(&'static str);
+
+    impl Puzzle for Versioned {
+        fn day(&self) -> usize {
+            26
+        }
+
+        fn title(&self) -> &'static str {
+            "Versioned"
+        }
+
+        fn description(&self) -> &'static str {
+            "Count the input"
+        }
+
+        fn version(&self) -> &'static str {
+            self.0
+        }
+
+        fn solve(&self, input: &str) -> Result<Vec<Answer>, String> {
+            Ok(vec![Answer::new(input.len(), format!("Counted {}", input.len()))])
+        }
+    }
+
+    #[test]
+    fn hash_input_is_fnv1a() {
+        assert_eq!(hash_input(b""), 0xcbf29ce484222325);
+        assert_eq!(hash_input(b"a"), 0xaf63dc4c8601ec8c);
+        assert_eq!(digest_input(b""), 0x6c62272e07bb014262b821756295c58d);
+        assert_eq!(digest_input(b"a"), 0xd228cb696f1a8caf78912b704e4a8964);
+    }
+
+    #[test]
+    fn answers_round_trip_by_input_and_version() {
+        let dir = std::env::temp_dir().join(format!("aoc-cache-{}", std::process::id()));
+        let cache = AnswerCache::new(&dir);
+        let answers = vec![
+            Answer::new(1, "First is 1".to_string()).explained_by(vec!["because".to_string()]),
+            Answer::new(2, "Second is 2".to_string()),
+        ];
+
+        assert_eq!(cache.get_all(&Versioned("1"), "input"), None);
+        cache.put_all(&Versioned("1"), "input", &answers).unwrap();
+
+        let stored = cache.get_all(&Versioned("1"), "input");
+        let other_input = cache.get_all(&Versioned("1"), "other input");
+        let other_version = cache.get_all(&Versioned("2"), "input");
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(stored, Some(answers));
+        assert_eq!(other_input, None);
+        assert_eq!(other_version, None);
+    }
+
+    #[test]
+    fn fields_keep_their_newlines() {
+        let dir = std::env::temp_dir().join(format!("aoc-cache-newlines-{}", std::process::id()));
+        let cache = AnswerCache::new(&dir);
+        let answers = vec![
+            Answer::new("1\n2", "Two\nlines".to_string()).explained_by(vec!["a\nb".to_string(), "".to_string()]),
+            Answer::new("", "".to_string()),
+        ];
+
+        cache.put_all(&Versioned("1"), "input", &answers).unwrap();
+        let stored = cache.get_all(&Versioned("1"), "input");
+        let files = std::fs::read_dir(cache.entry_dir(&Versioned("1"), "input")).unwrap().count();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(stored, Some(answers));
+        assert_eq!(files, 1);
+    }
+
+    #[test]
+    fn truncated_entries_are_misses() {
+        let data = encode_answers("input", &[Answer::new(12, "Twelve".to_string()).explained_by(vec!["x".to_string()])]);
+
+        assert!(decode_answers(&data, "input").is_some());
+        for end in 0..data.len() {
+            assert_eq!(decode_answers(&data[..end], "input"), None, "{:?}", &data[..end]);
+        }
+        assert_eq!(decode_answers(&format!("{}extra", data), "input"), None);
+        assert_eq!(decode_answers("1\n0\n", ""), None);
+    }
+
+    #[test]
+    fn entries_for_other_inputs_are_misses() {
+        let data = encode_answers("input", &[Answer::new(12, "Twelve".to_string())]);
+
+        assert!(decode_answers(&data, "input").is_some());
+        assert_eq!(decode_answers(&data, "inputs"), None);
+        assert_eq!(decode_answers(&data, "INPUT"), None);
+        let answers = vec![Answer::new(12, "Twelve".to_string())];
+        assert_eq!(decode_answers(&encode_answers("", &answers), ""), Some(answers));
+    }
+}
@@ -1,8 +1,9 @@
+use std::io::BufRead;
+use std::path::{Path, PathBuf};
 
 // Load the day's input from file
 pub fn load_input_from_file(day: &str) -> Result<String, String> {
-    let cargo_path = env!("CARGO_MANIFEST_DIR");
-    let input_file_path = format!("{}/share/days_of_advent/{}/input", cargo_path, &day);
+    let input_file_path = share_dir(day).join("input");
 
     match std::fs::read_to_string(&input_file_path) {
         Ok(data) => Ok(data),
@@ -10,7 +11,304 @@ pub fn load_input_from_file(day: &str) -> Result<String, String> {
     }
 }
 
+// The directory holding the day's inputs
+pub fn share_dir(day: &str) -> PathBuf {
+    let cargo_path = env!("CARGO_MANIFEST_DIR");
+    Path::new(cargo_path).join("share").join("days_of_advent").join(day)
+}
+
+/// One of several puzzle inputs for a day, with the answers expected for it
+#[derive(Debug, PartialEq)]
+pub struct NamedInput {
+    pub name: String,
+    pub data: String,
+    pub expected: Option<Vec<String>>,
+}
+
+/// Load every `*.txt` file in a directory as a puzzle input, named after the
+/// file stem. A `<name>.expected` file next to an input holds its expected
+/// answers, one part per line
+pub fn load_inputs_from_dir(dir: &Path) -> Result<Vec<NamedInput>, String> {
+    let entries = std::fs::read_dir(dir)
+        .map_err(|e| format!("Could not read {}: {}", dir.display(), e))?;
+
+    let mut paths = entries
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| p.extension().is_some_and(|ext| ext == "txt"))
+        .collect::<Vec<PathBuf>>();
+    paths.sort();
+
+    paths
+        .iter()
+        .map(|path| {
+            let name = path.file_stem().unwrap().to_string_lossy().to_string();
+            let data = std::fs::read_to_string(path)
+                .map_err(|e| format!("Could not read {}: {}", path.display(), e))?;
+            let expected = std::fs::read_to_string(path.with_extension("expected"))
+                .ok()
+                .map(|e| e.lines().map(|l| l.trim().to_string()).collect());
+
+            Ok(NamedInput { name, data, expected })
+        })
+        .collect()
+}
+
+/// Open an input file for reading a line or record at a time
+pub fn open_input(path: &Path) -> Result<std::io::BufReader<std::fs::File>, String> {
+    std::fs::File::open(path)
+        .map(std::io::BufReader::new)
+        .map_err(|e| format!("Could not open {}: {}", path.display(), e))
+}
+
+/// Lazily read the lines of an input, without their line endings
+pub fn lines<R: BufRead>(reader: R) -> impl Iterator<Item = Result<String, String>> {
+    reader.lines().map(|l| l.map_err(|e| e.to_string()))
+}
+
+/// Lazily read the blank-line separated records of an input
+pub fn records<R: BufRead>(reader: R) -> Records<R> {
+    Records { lines: reader.lines() }
+}
+
+/// An iterator over blank-line separated records, each yielded as its lines
+/// joined by `\n`. Only one record is held in memory at a time
+pub struct Records<R: BufRead> {
+    lines: std::io::Lines<R>,
+}
+
+impl<R: BufRead> Iterator for Records<R> {
+    type Item = Result<String, String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut record = String::new();
+        for line in &mut self.lines {
+            let line = match line {
+                Ok(line) => line,
+                Err(e) => return Some(Err(e.to_string())),
+            };
+            if line.trim().is_empty() {
+                if !record.is_empty() {
+                    return Some(Ok(record));
+                }
+            } else {
+                if !record.is_empty() {
+                    record.push('\n');
+                }
+                record.push_str(&line);
+            }
+        }
+
+        match record.is_empty() {
+            true => None,
+            false => Some(Ok(record)),
+        }
+    }
+}
+
+/// A whole puzzle input with its line endings normalised to `\n` and any
+/// blank lines at the end dropped, so that every day sees the same lines
+/// whether the file came from Windows, Unix, or an editor that adds newlines
+#[derive(Debug, Clone, PartialEq)]
+pub struct PuzzleInput {
+    text: String,
+}
+
+impl PuzzleInput {
+    pub fn new(raw: &str) -> Self {
+        let text = raw.replace("\r\n", "\n").replace('\r', "\n");
+        let mut lines: Vec<&str> = text.lines().collect();
+        while lines.last().is_some_and(|l| l.trim().is_empty()) {
+            lines.pop();
+        }
+
+        PuzzleInput { text: lines.join("\n") }
+    }
+
+    /// Every line, without its line ending. Blank lines between other lines
+    /// are kept, so line numbers match the file
+    pub fn lines(&self) -> impl Iterator<Item = &str> {
+        self.text.lines()
+    }
+
+    /// Runs of lines separated by one or more blank lines, such as passports
+    /// or customs groups
+    pub fn groups(&self) -> Vec<Vec<&str>> {
+        let mut groups = vec![];
+        let mut group = vec![];
+        for line in self.lines() {
+            if line.trim().is_empty() {
+                if !group.is_empty() {
+                    groups.push(std::mem::take(&mut group));
+                }
+            } else {
+                group.push(line);
+            }
+        }
+        if !group.is_empty() {
+            groups.push(group);
+        }
+        groups
+    }
+
+    /// The input as a rectangular grid of characters
+    pub fn grid(&self) -> Result<Grid, String> {
+        let rows: Vec<&str> = self.lines().skip_while(|l| l.trim().is_empty()).collect();
+        let width = rows.first().map(|r| r.chars().count()).ok_or("The puzzle input is empty")?;
+
+        let mut cells = Vec::with_capacity(width * rows.len());
+        for (idx, row) in rows.iter().enumerate() {
+            let len = row.chars().count();
+            if len != width {
+                return Err(format!("Row {} has {} columns, but the first row has {}", idx + 1, len, width));
+            }
+            cells.extend(row.chars());
+        }
+
+        Ok(Grid { cells, width })
+    }
+
+    /// Parse each line as one number, paired with its line counting from 1.
+    /// Blank lines hold no number, and a line holding more than one is an
+    /// error
+    pub fn numbers<T: std::str::FromStr>(&self) -> Result<Vec<(usize, T)>, String>
+    where
+        T::Err: std::fmt::Display,
+    {
+        let mut numbers = vec![];
+        for (idx, line) in self.lines().enumerate() {
+            let mut tokens = line.split_whitespace();
+            let token = match tokens.next() {
+                Some(token) => token,
+                None => continue,
+            };
+            if tokens.next().is_some() {
+                return Err(format!("Line {}: expected one number, found '{}'", idx + 1, line.trim()));
+            }
+            let number = token
+                .parse::<T>()
+                .map_err(|e| format!("Line {}: '{}' is not a number ({})", idx + 1, token, e))?;
+            numbers.push((idx + 1, number));
+        }
+        Ok(numbers)
+    }
+}
+
+/// A rectangular grid of characters, indexed by row then column
+#[derive(Debug, PartialEq)]
+pub struct Grid {
+    cells: Vec<char>,
+    width: usize,
+}
+
+impl Grid {
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        match self.width {
+            0 => 0,
+            width => self.cells.len() / width,
+        }
+    }
+
+    pub fn get(&self, row: usize, column: usize) -> Option<char> {
+        match column < self.width {
+            true => self.cells.get(row * self.width + column).copied(),
+            false => None,
+        }
+    }
+}
+
 pub fn format_day_report(day_num: usize, day_title: &str, day_description: &str, content: &str) -> String {
-    let formatted_content : String = content.lines().map(|s| format!("\t{}\n", s.to_string())).collect();
+    let formatted_content : String = content.lines().map(|s| format!("\t{}\n", s)).collect();
     format!("Day {}: {}\n{}\n\n{}", day_num, day_title, day_description, formatted_content)
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn load_inputs_from_dir_pairs_expected_answers() {
+        let dir = std::env::temp_dir().join(format!("aoc-inputs-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("bob.txt"), "2\n").unwrap();
+        std::fs::write(dir.join("alice.txt"), "1\n").unwrap();
+        std::fs::write(dir.join("alice.expected"), "10\n20\n").unwrap();
+        std::fs::write(dir.join("notes.md"), "ignored").unwrap();
+
+        let inputs = load_inputs_from_dir(&dir).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(inputs.len(), 2);
+        assert_eq!(inputs[0].name, "alice");
+        assert_eq!(inputs[0].expected, Some(vec!["10".to_string(), "20".to_string()]));
+        assert_eq!(inputs[1].data, "2\n");
+        assert_eq!(inputs[1].expected, None);
+    }
+
+    #[test]
+    fn lines_strip_line_endings() {
+        let input = "abc\r\ndef\n\nghi";
+
+        let result = lines(input.as_bytes()).collect::<Result<Vec<String>, String>>().unwrap();
+
+        assert_eq!(result, ["abc", "def", "", "ghi"]);
+    }
+
+    #[test]
+    fn records_split_on_blank_lines() {
+        let input = "\n\na b\nc\n\n\nd\r\n\r\ne f\n\n";
+
+        let result = records(input.as_bytes()).collect::<Result<Vec<String>, String>>().unwrap();
+
+        assert_eq!(result, ["a b\nc", "d", "e f"]);
+    }
+
+    #[test]
+    fn puzzle_input_normalises_line_endings() {
+        let unix = PuzzleInput::new("ab\ncd\n\nef\n\n\n");
+        let windows = PuzzleInput::new("ab\r\ncd\r\n\r\nef\r\n\r\n");
+
+        assert_eq!(unix, windows);
+        assert_eq!(unix.lines().collect::<Vec<&str>>(), ["ab", "cd", "", "ef"]);
+    }
+
+    #[test]
+    fn puzzle_input_groups_on_blank_lines() {
+        let input = PuzzleInput::new("\na b\nc\n\n  \nd\r\n\r\ne f\n\n");
+
+        assert_eq!(input.groups(), [vec!["a b", "c"], vec!["d"], vec!["e f"]]);
+    }
+
+    #[test]
+    fn puzzle_input_grid() {
+        let grid = PuzzleInput::new("..#\r\n#..\r\n").grid().unwrap();
+
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.get(0, 2), Some('#'));
+        assert_eq!(grid.get(1, 1), Some('.'));
+        assert_eq!(grid.get(0, 3), None);
+        assert_eq!(grid.get(2, 0), None);
+        assert!(PuzzleInput::new("..#\n#.\n").grid().is_err());
+        assert!(PuzzleInput::new("\n").grid().is_err());
+    }
+
+    #[test]
+    fn puzzle_input_numbers() {
+        let input = PuzzleInput::new("1721\r\n979\r\n\r\n -366 \r\n\r\n");
+
+        assert_eq!(input.numbers::<i32>(), Ok(vec![(1, 1721), (2, 979), (4, -366)]));
+        assert_eq!(input.numbers::<u32>(), Err("Line 4: '-366' is not a number (invalid digit found in string)".to_string()));
+        assert_eq!(
+            PuzzleInput::new("1721\n979 366\n").numbers::<i32>(),
+            Err("Line 2: expected one number, found '979 366'".to_string())
+        );
+    }
+
+    #[test]
+    fn load_inputs_from_missing_dir() {
+        assert!(load_inputs_from_dir(Path::new("/no/such/inputs")).is_err());
+    }
+}
@@ -1 +1,10 @@
-pub mod io;
\ No newline at end of file
+pub mod batch;
+pub mod budget;
+pub mod cache;
+pub mod io;
+pub mod puzzle;
+pub mod runner;
+pub mod scaffold;
+pub mod server;
+#[cfg(test)]
+pub mod snapshot;
@@ -0,0 +1,83 @@
+use super::budget::Budget;
+
+/// The answer to one part of a day's puzzle, with the steps that led to it
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Answer {
+    pub value: String,
+    pub summary: String,
+    pub explanation: Vec<String>,
+}
+
+impl Answer {
+    pub fn new(value: impl ToString, summary: String) -> Self {
+        Answer {
+            value: value.to_string(),
+            summary,
+            explanation: vec![],
+        }
+    }
+
+    pub fn explained_by(mut self, explanation: Vec<String>) -> Self {
+        self.explanation = explanation;
+        self
+    }
+}
+
+/// Everything a day's report shows: its heading and the answer to each part
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Report {
+    pub day: usize,
+    pub title: String,
+    pub description: String,
+    pub answers: Vec<Answer>,
+}
+
+impl Report {
+    pub fn new(puzzle: &dyn Puzzle, answers: Vec<Answer>) -> Self {
+        Report {
+            day: puzzle.day(),
+            title: puzzle.title().to_string(),
+            description: puzzle.description().to_string(),
+            answers,
+        }
+    }
+}
+
+/// A day of advent that can be solved from its raw puzzle input
+pub trait Puzzle {
+    fn day(&self) -> usize;
+    fn title(&self) -> &'static str;
+    fn description(&self) -> &'static str;
+
+    /// Identifies the solver in the answer cache. Change it whenever a
+    /// change to the solver changes its answers
+    fn version(&self) -> &'static str {
+        "1"
+    }
+
+    /// Parse the input and solve each part of the puzzle, in order
+    fn solve(&self, input: &str) -> Result<Vec<Answer>, String>;
+
+    /// Solve the puzzle, giving up once `budget` is spent. Days whose solvers
+    /// could run away on hostile input override this to check the budget as
+    /// they go; the rest are only checked once they finish
+    fn solve_within(&self, input: &str, budget: &Budget) -> Result<Vec<Answer>, String> {
+        let answers = self.solve(input)?;
+        budget.check_time()?;
+        Ok(answers)
+    }
+
+    /// Solve the puzzle from a reader. Days whose input can be handled a
+    /// line or record at a time override this to run in constant memory
+    fn solve_reader(&self, reader: &mut dyn std::io::BufRead, budget: &Budget) -> Result<Vec<Answer>, String> {
+        let mut input = String::new();
+        reader.read_to_string(&mut input).map_err(|e| e.to_string())?;
+        self.solve_within(&input, budget)
+    }
+
+    fn name(&self) -> String {
+        format!("day{:02}", self.day())
+    }
+}
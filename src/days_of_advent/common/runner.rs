@@ -0,0 +1,215 @@
+use super::budget::BudgetLimits;
+use super::cache::AnswerCache;
+use super::io;
+use super::puzzle::{Answer, Puzzle, Report};
+
+#[derive(Debug, Default, PartialEq)]
+pub struct RunOptions {
+    /// Show how each answer was derived beneath its summary
+    pub explain: bool,
+    /// Stream the input from this file instead of loading the day's own input.
+    /// Streamed inputs are never cached
+    pub input: Option<std::path::PathBuf>,
+    /// Reuse answers already computed for the same input and solver version
+    pub cache: Option<AnswerCache>,
+    /// Recompute answers even when cached, replacing what was stored
+    pub refresh: bool,
+    /// How long each solver may run and how many steps it may take
+    pub budget: BudgetLimits,
+}
+
+/// Load a day's input, solve it, and format the report for printing
+pub fn run_puzzle(puzzle: &dyn Puzzle, options: &RunOptions) -> Result<String, String> {
+    let answers = match &options.input {
+        Some(path) => puzzle.solve_reader(&mut io::open_input(path)?, &options.budget.start())?,
+        None => {
+            let puzzle_input = io::load_input_from_file(&puzzle.name())
+                .map_err(|e| format!("Could not load input for {}: {}", puzzle.name(), e))?;
+            solve_with_cache(puzzle, &puzzle_input, options)?
+        }
+    };
+
+    Ok(render_report(&Report::new(puzzle, answers), options))
+}
+
+/// Solve a puzzle, going through the answer cache when one is in use
+pub fn solve_with_cache(puzzle: &dyn Puzzle, input: &str, options: &RunOptions) -> Result<Vec<Answer>, String> {
+    let cache = match &options.cache {
+        Some(cache) => cache,
+        None => return puzzle.solve_within(input, &options.budget.start()),
+    };
+
+    if !options.refresh {
+        if let Some(answers) = cache.get_all(puzzle, input) {
+            return Ok(answers);
+        }
+    }
+
+    let answers = puzzle.solve_within(input, &options.budget.start())?;
+    if let Err(e) = cache.put_all(puzzle, input, &answers) {
+        eprintln!("Could not cache answers for {}: {}", puzzle.name(), e);
+    }
+    Ok(answers)
+}
+
+/// Solve a puzzle as `solve_with_cache` does, but turn a panic in the solver
+/// into an error, so that one bad input cannot bring down a batch or server
+pub fn solve_guarded(puzzle: &dyn Puzzle, input: &str, options: &RunOptions) -> Result<Vec<Answer>, String> {
+    std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| solve_with_cache(puzzle, input, options)))
+        .unwrap_or_else(|payload| {
+            let message = payload
+                .downcast_ref::<String>()
+                .map(|m| m.as_str())
+                .or_else(|| payload.downcast_ref::<&str>().copied())
+                .unwrap_or("unknown cause");
+            Err(format!("Solver panicked: {}", message))
+        })
+}
+
+pub fn render_report(report: &Report, options: &RunOptions) -> String {
+    let mut lines: Vec<String> = vec![];
+    for answer in &report.answers {
+        lines.push(answer.summary.clone());
+        if options.explain {
+            lines.extend(answer.explanation.iter().map(|e| format!("    {}", e)));
+        }
+    }
+    let content = lines.join("\n");
+
+    io::format_day_report(report.day, &report.title, &report.description, &content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days_of_advent::common::budget::Budget;
+
+    struct Echo;
+
+    impl Puzzle for Echo {
+        fn day(&self) -> usize {
+            26
+        }
+
+        fn title(&self) -> &'static str {
+            "Echo"
+        }
+
+        fn description(&self) -> &'static str {
+            "Repeat the input"
+        }
+
+        fn solve(&self, input: &str) -> Result<Vec<Answer>, String> {
+            Ok(vec![Answer::new(input, format!("Heard {}", input))])
+        }
+    }
+
+    #[test]
+    fn render_report_indents_each_summary() {
+        let answers = vec![
+            Answer::new(1, "First is 1".to_string()),
+            Answer::new(2, "Second is 2".to_string()),
+        ];
+
+        let report = render_report(&Report::new(&Echo, answers), &RunOptions::default());
+
+        assert_eq!(report, "Day 26: Echo\nRepeat the input\n\n\tFirst is 1\n\tSecond is 2\n");
+    }
+
+    #[test]
+    fn render_report_explains_answers_when_asked() {
+        let answers = vec![
+            Answer::new(1, "First is 1".to_string()).explained_by(vec!["0 + 1 = 1".to_string()]),
+            Answer::new(2, "Second is 2".to_string()),
+        ];
+
+        let report = Report::new(&Echo, answers);
+
+        let quiet = render_report(&report, &RunOptions::default());
+        let explained = render_report(&report, &RunOptions { explain: true, ..Default::default() });
+
+        assert!(!quiet.contains("0 + 1 = 1"));
+        assert_eq!(
+            explained,
+            "Day 26: Echo\nRepeat the input\n\n\tFirst is 1\n\t    0 + 1 = 1\n\tSecond is 2\n"
+        );
+    }
+
+    #[test]
+    fn solve_reader_defaults_to_solve() {
+        let answers = Echo.solve_reader(&mut "hello".as_bytes(), &Budget::unlimited()).unwrap();
+
+        assert_eq!(answers, Echo.solve("hello").unwrap());
+    }
+
+    #[test]
+    fn solve_with_cache_reuses_and_refreshes_answers() {
+        let dir = std::env::temp_dir().join(format!("aoc-runner-cache-{}", std::process::id()));
+        let cache = AnswerCache::new(&dir);
+        cache.put_all(&Echo, "hello", &[Answer::new("stale", "Heard stale".to_string())]).unwrap();
+
+        let mut options = RunOptions { cache: Some(cache), ..Default::default() };
+        let cached = solve_with_cache(&Echo, "hello", &options).unwrap();
+        options.refresh = true;
+        let refreshed = solve_with_cache(&Echo, "hello", &options).unwrap();
+        options.refresh = false;
+        let stored = solve_with_cache(&Echo, "hello", &options).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(cached[0].value, "stale");
+        assert_eq!(refreshed[0].value, "hello");
+        assert_eq!(stored[0].value, "hello");
+    }
+
+    #[test]
+    fn solve_guarded_reports_panics() {
+        struct Panics;
+
+        impl Puzzle for Panics {
+            fn day(&self) -> usize {
+                26
+            }
+
+            fn title(&self) -> &'static str {
+                "Panics"
+            }
+
+            fn description(&self) -> &'static str {
+                "Fail loudly"
+            }
+
+            fn solve(&self, input: &str) -> Result<Vec<Answer>, String> {
+                let value = input.parse::<u8>().expect("Input was not a number");
+                Ok(vec![Answer::new(value, format!("Read {}", value))])
+            }
+        }
+
+        let options = RunOptions::default();
+
+        assert_eq!(solve_guarded(&Panics, "7", &options).unwrap()[0].value, "7");
+        let error = solve_guarded(&Panics, "seven", &options).unwrap_err();
+        assert!(error.starts_with("Solver panicked: Input was not a number"));
+    }
+
+    #[test]
+    fn puzzle_name_is_zero_padded() {
+        assert_eq!(Echo.name(), "day26");
+        let answers = Echo.solve("hello").unwrap();
+        assert_eq!(answers[0].value, "hello");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn report_round_trips_through_json() {
+        let answers = vec![Answer::new(1, "First is 1".to_string()).explained_by(vec!["0 + 1 = 1".to_string()])];
+        let report = Report::new(&Echo, answers);
+
+        let json = serde_json::to_string(&report).unwrap();
+
+        assert_eq!(
+            json,
+            r#"{"day":26,"title":"Echo","description":"Repeat the input","answers":[{"value":"1","summary":"First is 1","explanation":["0 + 1 = 1"]}]}"#
+        );
+        assert_eq!(serde_json::from_str::<Report>(&json).unwrap(), report);
+    }
+}
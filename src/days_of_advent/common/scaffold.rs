@@ -0,0 +1,271 @@
+use std::path::{Path, PathBuf};
+
+const DAY_TEMPLATE: &str = r#"use crate::days_of_advent::common::puzzle::{Answer, Puzzle};
+
+/// The Day __DAY__ puzzle
+pub struct __TYPE__;
+
+impl Puzzle for __TYPE__ {
+    fn day(&self) -> usize {
+        __NUM__
+    }
+
+    fn title(&self) -> &'static str {
+        __TITLE__
+    }
+
+    fn description(&self) -> &'static str {
+        __DESCRIPTION__
+    }
+
+    fn solve(&self, input: &str) -> Result<Vec<Answer>, String> {
+        let entries = parse(input)?;
+
+        let part1_answer = part1(&entries)?;
+        let part2_answer = part2(&entries)?;
+
+        Ok(vec![
+            Answer::new(part1_answer, format!("Part 1 answer is {}", part1_answer)),
+            Answer::new(part2_answer, format!("Part 2 answer is {}", part2_answer)),
+        ])
+    }
+}
+
+fn parse(input: &str) -> Result<Vec<String>, String> {
+    Ok(input.lines().map(|l| l.to_string()).collect())
+}
+
+fn part1(entries: &[String]) -> Result<usize, String> {
+    Err(format!("Part 1 is not solved yet, given {} entries", entries.len()))
+}
+
+fn part2(entries: &[String]) -> Result<usize, String> {
+    Err(format!("Part 2 is not solved yet, given {} entries", entries.len()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[ignore]
+    fn provided_acceptance_test() {
+        let input = "";
+
+        let entries = parse(input).unwrap();
+
+        assert_eq!(part1(&entries), Ok(0));
+    }
+}
+"#;
+
+/// Create a new day module from the template, give it a share directory for
+/// its input, and register it with the other days. Returns the created paths
+pub fn new_day(day: usize, title: &str, description: &str) -> Result<Vec<PathBuf>, String> {
+    new_day_in(Path::new(env!("CARGO_MANIFEST_DIR")), day, title, description)
+}
+
+pub fn new_day_in(
+    root: &Path,
+    day: usize,
+    title: &str,
+    description: &str,
+) -> Result<Vec<PathBuf>, String> {
+    if !(1..=25).contains(&day) {
+        return Err(format!("Day {} is not a day of advent", day));
+    }
+    let type_name = type_name_from_title(title)?;
+    let module = format!("day{:02}", day);
+
+    let days_dir = root.join("src").join("days_of_advent");
+    let module_dir = days_dir.join(&module);
+    if module_dir.exists() || days_dir.join(format!("{}.rs", module)).exists() {
+        return Err(format!("Module {} already exists", module));
+    }
+
+    let registry_path = days_dir.join("mod.rs");
+    let registry = std::fs::read_to_string(&registry_path).map_err(|e| e.to_string())?;
+    let registry = register_day(&registry, day, &type_name)?;
+
+    let module_path = module_dir.join("mod.rs");
+    let share_dir = root.join("share").join("days_of_advent").join(&module);
+    let input_path = share_dir.join("input");
+
+    std::fs::create_dir_all(&module_dir).map_err(|e| e.to_string())?;
+    std::fs::write(&module_path, render_day_module(day, &type_name, title, description))
+        .map_err(|e| e.to_string())?;
+    std::fs::create_dir_all(&share_dir).map_err(|e| e.to_string())?;
+    if !input_path.exists() {
+        std::fs::write(&input_path, "").map_err(|e| e.to_string())?;
+    }
+    std::fs::write(&registry_path, registry).map_err(|e| e.to_string())?;
+
+    Ok(vec![module_path, input_path, registry_path])
+}
+
+fn render_day_module(day: usize, type_name: &str, title: &str, description: &str) -> String {
+    DAY_TEMPLATE
+        .replace("__DAY__", &format!("{:02}", day))
+        .replace("__NUM__", &day.to_string())
+        .replace("__TYPE__", type_name)
+        .replace("__TITLE__", &format!("{:?}", title))
+        .replace("__DESCRIPTION__", &format!("{:?}", description))
+}
+
+/// Turn a puzzle title such as "Handy Haversacks" into a type name such as
+/// `HandyHaversacks`
+fn type_name_from_title(title: &str) -> Result<String, String> {
+    let type_name: String = title
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(|w| {
+            let mut chars = w.chars();
+            let first = chars.next().unwrap().to_ascii_uppercase();
+            std::iter::once(first).chain(chars).collect::<String>()
+        })
+        .collect();
+
+    match type_name.chars().next() {
+        Some(c) if c.is_ascii_alphabetic() => Ok(type_name),
+        _ => Err(format!("Could not make a type name from title '{}'", title)),
+    }
+}
+
+/// Add the `pub mod` declaration and the registry entry for a day to the
+/// source of `days_of_advent/mod.rs`, keeping both in day order
+fn register_day(registry: &str, day: usize, type_name: &str) -> Result<String, String> {
+    let module = format!("day{:02}", day);
+    let mut lines: Vec<String> = registry.lines().map(|l| l.to_string()).collect();
+
+    let mod_line = format!("pub mod {};", module);
+    let mod_idx = insertion_index(&lines, "pub mod day", day)
+        .or_else(|| lines.iter().position(|l| l.trim() == "pub mod common;").map(|i| i + 1))
+        .ok_or("Could not find the day module declarations")?;
+    lines.insert(mod_idx, mod_line);
+
+    let entry_indent = lines
+        .iter()
+        .find(|l| l.trim_start().starts_with("Box::new(day"))
+        .map(|l| l[..l.len() - l.trim_start().len()].to_string())
+        .ok_or("Could not find the puzzle registry")?;
+    let entry_idx = insertion_index(&lines, "Box::new(day", day)
+        .ok_or("Could not find the puzzle registry")?;
+    lines.insert(entry_idx, format!("{}Box::new({}::{}),", entry_indent, module, type_name));
+
+    let mut source = lines.join("\n");
+    if registry.ends_with('\n') {
+        source.push('\n');
+    }
+    Ok(source)
+}
+
+/// Find the line after the last `prefix`-led line whose day number is lower
+/// than `day`, or before the first one if every day is higher
+fn insertion_index(lines: &[String], prefix: &str, day: usize) -> Option<usize> {
+    let numbered: Vec<(usize, usize)> = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, l)| {
+            let rest = l.trim_start().strip_prefix(prefix)?;
+            let digits: String = rest.chars().take_while(|c| c.is_ascii_digit()).collect();
+            digits.parse::<usize>().ok().map(|d| (idx, d))
+        })
+        .collect();
+
+    match numbered.iter().rfind(|(_, d)| *d < day) {
+        Some((idx, _)) => Some(idx + 1),
+        None => numbered.first().map(|(idx, _)| *idx),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REGISTRY: &str = "\
+pub mod common;
+pub mod day01;
+pub mod day03;
+
+use common::puzzle::Puzzle;
+
+pub fn puzzles() -> Vec<Box<dyn Puzzle>> {
+    vec![
+        Box::new(day01::RepairReport),
+        Box::new(day03::TobogganTrajectory),
+    ]
+}
+";
+
+    #[test]
+    fn type_name_from_title_camel_cases_words() {
+        assert_eq!(type_name_from_title("Handy Haversacks").unwrap(), "HandyHaversacks");
+        assert_eq!(type_name_from_title("encoding-error").unwrap(), "EncodingError");
+        assert!(type_name_from_title("2020").is_err());
+        assert!(type_name_from_title("  ").is_err());
+    }
+
+    #[test]
+    fn register_day_keeps_day_order() {
+        let result = register_day(REGISTRY, 2, "PasswordPhilosophy").unwrap();
+
+        let expected = "\
+pub mod common;
+pub mod day01;
+pub mod day02;
+pub mod day03;
+
+use common::puzzle::Puzzle;
+
+pub fn puzzles() -> Vec<Box<dyn Puzzle>> {
+    vec![
+        Box::new(day01::RepairReport),
+        Box::new(day02::PasswordPhilosophy),
+        Box::new(day03::TobogganTrajectory),
+    ]
+}
+";
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn register_day_appends_later_days() {
+        let result = register_day(REGISTRY, 9, "EncodingError").unwrap();
+
+        assert!(result.contains("pub mod day03;\npub mod day09;\n"));
+        assert!(result.contains(
+            "        Box::new(day03::TobogganTrajectory),\n        Box::new(day09::EncodingError),\n"
+        ));
+    }
+
+    #[test]
+    fn render_day_module_fills_in_template() {
+        let source = render_day_module(9, "EncodingError", "Encoding \"Error\"", "Find the weakness");
+
+        assert!(source.contains("/// The Day 09 puzzle\npub struct EncodingError;"));
+        assert!(source.contains("impl Puzzle for EncodingError {"));
+        assert!(source.contains("        9\n"));
+        assert!(source.contains(r#""Encoding \"Error\"""#));
+        assert!(source.contains(r#""Find the weakness""#));
+        assert!(!source.contains("__"));
+    }
+
+    #[test]
+    fn new_day_in_creates_and_registers_module() {
+        let root = std::env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+        let days_dir = root.join("src").join("days_of_advent");
+        std::fs::create_dir_all(&days_dir).unwrap();
+        std::fs::write(days_dir.join("mod.rs"), REGISTRY).unwrap();
+
+        let created = new_day_in(&root, 9, "Encoding Error", "Find the weakness").unwrap();
+
+        assert_eq!(created.len(), 3);
+        assert!(days_dir.join("day09").join("mod.rs").exists());
+        assert!(root.join("share/days_of_advent/day09/input").exists());
+        let registry = std::fs::read_to_string(days_dir.join("mod.rs")).unwrap();
+        assert!(registry.contains("Box::new(day09::EncodingError),"));
+        assert!(new_day_in(&root, 9, "Encoding Error", "Again").is_err());
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
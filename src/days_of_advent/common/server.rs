@@ -0,0 +1,383 @@
+use std::io::{BufRead, BufReader, ErrorKind, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::time::{Duration, Instant};
+
+use super::puzzle::Puzzle;
+use super::runner::{self, RunOptions};
+
+/// The largest puzzle input the server will read
+const MAX_BODY_BYTES: usize = 64 * 1024 * 1024;
+
+/// The most the request line and headers together may take up
+const MAX_HEADER_BYTES: u64 = 16 * 1024;
+
+/// How long a client may take to send its whole request, or to read the
+/// response. Requests are answered one at a time, so a slow client would
+/// otherwise hold up every other
+const CONNECTION_TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Debug, PartialEq)]
+pub struct HttpResponse {
+    pub status: u16,
+    pub body: String,
+}
+
+impl HttpResponse {
+    fn error(status: u16, message: &str) -> Self {
+        HttpResponse {
+            status,
+            body: format!("{{\"error\":{}}}", json_string(message)),
+        }
+    }
+
+    fn reason(&self) -> &'static str {
+        match self.status {
+            200 => "OK",
+            400 => "Bad Request",
+            404 => "Not Found",
+            405 => "Method Not Allowed",
+            408 => "Request Timeout",
+            413 => "Payload Too Large",
+            422 => "Unprocessable Entity",
+            431 => "Request Header Fields Too Large",
+            _ => "Internal Server Error",
+        }
+    }
+}
+
+/// Serve `POST /day/{n}/part/{p}` on `address` until the process is stopped,
+/// answering one request at a time
+pub fn serve(address: &str, puzzles: &[Box<dyn Puzzle>], options: &RunOptions) -> Result<(), String> {
+    let listener = TcpListener::bind(address).map_err(|e| format!("Could not bind {}: {}", address, e))?;
+    println!("Serving puzzles on http://{}", listener.local_addr().map_err(|e| e.to_string())?);
+
+    for stream in listener.incoming() {
+        match stream {
+            Ok(stream) => {
+                if let Err(e) = handle_connection(stream, puzzles, options, CONNECTION_TIMEOUT) {
+                    eprintln!("Could not answer request: {}", e);
+                }
+            }
+            Err(e) => eprintln!("Could not accept connection: {}", e),
+        }
+    }
+    Ok(())
+}
+
+/// Answer the request on `stream`, giving up on a client that takes longer
+/// than `timeout` to send the whole request, or to receive the response
+pub fn handle_connection(
+    stream: TcpStream,
+    puzzles: &[Box<dyn Puzzle>],
+    options: &RunOptions,
+    timeout: Duration,
+) -> Result<(), String> {
+    stream.set_write_timeout(Some(timeout)).map_err(|e| e.to_string())?;
+    let mut reader = BufReader::new(DeadlineReader {
+        stream: stream.try_clone().map_err(|e| e.to_string())?,
+        deadline: Instant::now() + timeout,
+    });
+
+    let response = match read_request(&mut reader) {
+        Ok((method, path, body)) => handle_request(puzzles, options, &method, &path, &body),
+        Err(response) => response,
+    };
+
+    let mut stream = stream;
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        response.status,
+        response.reason(),
+        response.body.len(),
+        response.body
+    )
+    .map_err(|e| e.to_string())
+}
+
+/// Reads from a stream until a fixed time, however the reads are spread out,
+/// so a client cannot hold the server by sending a byte at a time
+struct DeadlineReader {
+    stream: TcpStream,
+    deadline: Instant,
+}
+
+impl Read for DeadlineReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let remaining = self
+            .deadline
+            .checked_duration_since(Instant::now())
+            .filter(|remaining| !remaining.is_zero())
+            .ok_or_else(|| std::io::Error::new(ErrorKind::TimedOut, "The request took too long"))?;
+        self.stream.set_read_timeout(Some(remaining))?;
+        self.stream.read(buf)
+    }
+}
+
+/// Read the method, path, and body of a request. The request line and
+/// headers may take up at most `MAX_HEADER_BYTES`
+fn read_request(reader: &mut impl BufRead) -> Result<(String, String, Vec<u8>), HttpResponse> {
+    let bad_request = |message: &str| HttpResponse::error(400, message);
+    let read_error = |e: std::io::Error, message: &str| match e.kind() {
+        ErrorKind::WouldBlock | ErrorKind::TimedOut => HttpResponse::error(408, "Timed out reading the request"),
+        _ => bad_request(message),
+    };
+
+    let mut head = reader.by_ref().take(MAX_HEADER_BYTES);
+    let mut read_head_line = |message: &str| {
+        let mut line = String::new();
+        head.read_line(&mut line).map_err(|e| read_error(e, message))?;
+        match (line.ends_with('\n'), head.limit()) {
+            (true, _) => Ok(line),
+            (false, 0) => Err(HttpResponse::error(431, "The request line and headers are too large")),
+            (false, _) => Err(bad_request("The request ended before its headers did")),
+        }
+    };
+
+    let request_line = read_head_line("Could not read request")?;
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().ok_or_else(|| bad_request("Missing method"))?.to_string();
+    let path = parts.next().ok_or_else(|| bad_request("Missing path"))?.to_string();
+
+    let mut content_length = 0;
+    loop {
+        let header = read_head_line("Could not read headers")?;
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("content-length") {
+                content_length = value
+                    .trim()
+                    .parse::<usize>()
+                    .map_err(|_| bad_request("Content-Length is not a number"))?;
+            }
+        }
+    }
+
+    if content_length > MAX_BODY_BYTES {
+        return Err(HttpResponse::error(413, "Puzzle input is too large"));
+    }
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body).map_err(|e| read_error(e, "Body was shorter than Content-Length"))?;
+
+    Ok((method, path, body))
+}
+
+/// Route a request to a day's solver and describe its answer as JSON
+pub fn handle_request(
+    puzzles: &[Box<dyn Puzzle>],
+    options: &RunOptions,
+    method: &str,
+    path: &str,
+    body: &[u8],
+) -> HttpResponse {
+    let (day, part) = match parse_path(path) {
+        Some(day_part) => day_part,
+        None => return HttpResponse::error(404, "Expected a path like /day/{n}/part/{p}"),
+    };
+    if method != "POST" {
+        return HttpResponse::error(405, "Send the puzzle input with POST");
+    }
+    let puzzle = match puzzles.iter().find(|p| p.day() == day) {
+        Some(puzzle) => puzzle,
+        None => return HttpResponse::error(404, &format!("Day {} has not been solved yet", day)),
+    };
+    let input = match std::str::from_utf8(body) {
+        Ok(input) if !input.trim().is_empty() => input,
+        Ok(_) => return HttpResponse::error(400, "The puzzle input is empty"),
+        Err(e) => return HttpResponse::error(400, &format!("The puzzle input is not UTF-8: {}", e)),
+    };
+
+    let start = std::time::Instant::now();
+    let answers = runner::solve_guarded(puzzle.as_ref(), input, options);
+    let elapsed = start.elapsed();
+
+    let answers = match answers {
+        Ok(answers) => answers,
+        Err(e) => return HttpResponse::error(422, &e),
+    };
+    let answer = match part.checked_sub(1).and_then(|idx| answers.get(idx)) {
+        Some(answer) => answer,
+        None => return HttpResponse::error(404, &format!("Day {} has no part {}", day, part)),
+    };
+
+    HttpResponse {
+        status: 200,
+        body: format!(
+            "{{\"day\":{},\"part\":{},\"answer\":{},\"summary\":{},\"elapsed_ms\":{:.3}}}",
+            day,
+            part,
+            json_string(&answer.value),
+            json_string(&answer.summary),
+            elapsed.as_secs_f64() * 1000.0
+        ),
+    }
+}
+
+fn parse_path(path: &str) -> Option<(usize, usize)> {
+    let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+    match segments.as_slice() {
+        ["day", day, "part", part] => Some((day.parse().ok()?, part.parse().ok()?)),
+        _ => None,
+    }
+}
+
+/// Quote and escape a string for JSON
+pub fn json_string(s: &str) -> String {
+    let mut quoted = String::with_capacity(s.len() + 2);
+    quoted.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if (c as u32) < 0x20 => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days_of_advent;
+
+    fn post(path: &str, body: &str) -> HttpResponse {
+        handle_request(&days_of_advent::puzzles(), &RunOptions::default(), "POST", path, body.as_bytes())
+    }
+
+    #[test]
+    fn answers_a_part_as_json() {
+        let response = post("/day/1/part/2", "1721\n979\n366\n299\n675\n1456\n");
+
+        assert_eq!(response.status, 200);
+        assert!(response.body.starts_with("{\"day\":1,\"part\":2,\"answer\":\"241861950\","));
+        assert!(response.body.contains("\"elapsed_ms\":"));
+    }
+
+    #[test]
+    fn unknown_routes_and_methods() {
+        assert_eq!(post("/day/1", "1").status, 404);
+        assert_eq!(post("/day/25/part/1", "1").status, 404);
+        assert_eq!(post("/day/8/part/2", "nop +0\njmp +0\n").status, 404);
+        assert_eq!(post("/day/1/part/1", "").status, 400);
+
+        let response = handle_request(&days_of_advent::puzzles(), &RunOptions::default(), "GET", "/day/1/part/1", b"");
+        assert_eq!(response.status, 405);
+    }
+
+    #[test]
+    fn malformed_input_is_unprocessable() {
+        let response = post("/day/1/part/1", "1721\nnot a number\n");
+        let instructions = post("/day/8/part/1", "nop +0\nbogus\n");
+        let seats = post("/day/5/part/1", "FBFBBFFRLR\nFBFBBFFRL\n");
+
+        assert_eq!(response.status, 422);
+        assert_eq!(response.body, "{\"error\":\"Line 2: expected one number, found 'not a number', reading entries as i64\"}");
+        assert_eq!(instructions.status, 422);
+        assert_eq!(
+            instructions.body,
+            "{\"error\":\"Line 2: expected an instruction such as 'acc +1', found 'bogus'\"}"
+        );
+        assert_eq!(
+            seats.body,
+            "{\"error\":\"Line 2: 'FBFBBFFRL' has 9 characters, but a boarding pass has 10\"}"
+        );
+    }
+
+    #[test]
+    fn json_string_escapes() {
+        assert_eq!(json_string("a \"b\"\n\\"), "\"a \\\"b\\\"\\n\\\\\"");
+        assert_eq!(json_string("\u{1}"), "\"\\u0001\"");
+    }
+
+    #[test]
+    fn serves_over_tcp() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+
+        let client = std::thread::spawn(move || {
+            let mut stream = TcpStream::connect(address).unwrap();
+            let body = "1-3 a: abcde\n1-3 b: cdefg\n2-9 c: ccccccccc\n";
+            write!(stream, "POST /day/2/part/1 HTTP/1.1\r\nContent-Length: {}\r\n\r\n{}", body.len(), body).unwrap();
+            let mut response = String::new();
+            stream.read_to_string(&mut response).unwrap();
+            response
+        });
+
+        let (stream, _) = listener.accept().unwrap();
+        handle_connection(stream, &days_of_advent::puzzles(), &RunOptions::default(), CONNECTION_TIMEOUT).unwrap();
+        let response = client.join().unwrap();
+
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(response.contains("\"answer\":\"2\""));
+    }
+
+    #[test]
+    fn silent_clients_time_out() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+
+        let client = std::thread::spawn(move || {
+            let mut stream = TcpStream::connect(address).unwrap();
+            write!(stream, "POST /day/2/part/1 HTTP/1.1\r\n").unwrap();
+            let mut response = String::new();
+            stream.read_to_string(&mut response).unwrap();
+            response
+        });
+
+        let (stream, _) = listener.accept().unwrap();
+        let timeout = Duration::from_millis(50);
+        handle_connection(stream, &days_of_advent::puzzles(), &RunOptions::default(), timeout).unwrap();
+        let response = client.join().unwrap();
+
+        assert!(response.starts_with("HTTP/1.1 408 Request Timeout\r\n"));
+    }
+
+    #[test]
+    fn slow_clients_time_out() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+
+        // One byte at a time, each well within the timeout, for far longer
+        // than the timeout in all
+        let client = std::thread::spawn(move || {
+            let mut stream = TcpStream::connect(address).unwrap();
+            for byte in "POST /day/2/part/1 HTTP/1.1\r\nX-Slow: ".bytes().chain(std::iter::repeat(b'a')).take(200) {
+                if stream.write_all(&[byte]).is_err() {
+                    break;
+                }
+                std::thread::sleep(Duration::from_millis(10));
+            }
+        });
+
+        let (stream, _) = listener.accept().unwrap();
+        let start = Instant::now();
+        let _ = handle_connection(stream, &days_of_advent::puzzles(), &RunOptions::default(), Duration::from_millis(100));
+        let elapsed = start.elapsed();
+        client.join().unwrap();
+
+        assert!(elapsed < Duration::from_secs(1), "{:?}", elapsed);
+    }
+
+    #[test]
+    fn headers_are_bounded() {
+        let read = |request: &str| read_request(&mut request.as_bytes()).map(|(method, path, _)| (method, path));
+
+        let huge_header = format!("POST /day/1/part/1 HTTP/1.1\r\nX-Big: {}\r\n\r\n", "a".repeat(20_000));
+        assert_eq!(read(&huge_header).unwrap_err().status, 431);
+        let many_headers = format!("POST /day/1/part/1 HTTP/1.1\r\n{}\r\n", "X-Many: a\r\n".repeat(2_000));
+        assert_eq!(read(&many_headers).unwrap_err().status, 431);
+        assert_eq!(read("POST /day/1/part/1 HTTP/1.1\r\nX-Cut: a").unwrap_err().status, 400);
+        assert_eq!(
+            read("POST /day/1/part/1 HTTP/1.1\r\nHost: localhost\r\n\r\n"),
+            Ok(("POST".to_string(), "/day/1/part/1".to_string()))
+        );
+    }
+}
@@ -0,0 +1,145 @@
+use std::path::{Path, PathBuf};
+
+use super::io::{self, NamedInput};
+use super::puzzle::{Puzzle, Report};
+use super::runner::{self, RunOptions};
+
+/// Set this to rewrite the stored snapshots with the current output, after
+/// a change to a report that was meant
+pub const UPDATE_ENV: &str = "UPDATE_SNAPSHOTS";
+
+pub fn update_mode() -> bool {
+    std::env::var_os(UPDATE_ENV).is_some_and(|v| v != "0")
+}
+
+/// Where a day keeps the snapshots of its reports
+pub fn snapshot_dir(puzzle: &dyn Puzzle) -> PathBuf {
+    io::share_dir(&puzzle.name()).join("snapshots")
+}
+
+/// Every rendered form of a day's report for one input, keyed by the name of
+/// its snapshot file. The JSON form is only checked with the `serde` feature
+pub fn render_snapshots(puzzle: &dyn Puzzle, input: &NamedInput) -> Result<Vec<(String, String)>, String> {
+    let answers = runner::solve_guarded(puzzle, &input.data, &RunOptions::default())
+        .map_err(|e| format!("{} failed on {}: {}", puzzle.name(), input.name, e))?;
+    let report = Report::new(puzzle, answers);
+    let explained = RunOptions { explain: true, ..Default::default() };
+
+    #[allow(unused_mut)]
+    let mut snapshots = vec![
+        (
+            format!("{}.report", input.name),
+            runner::render_report(&report, &RunOptions::default()),
+        ),
+        (
+            format!("{}.explained.report", input.name),
+            runner::render_report(&report, &explained),
+        ),
+    ];
+
+    #[cfg(feature = "serde")]
+    snapshots.push((
+        format!("{}.json", input.name),
+        serde_json::to_string_pretty(&report).map_err(|e| e.to_string())? + "\n",
+    ));
+
+    Ok(snapshots)
+}
+
+/// Compare `actual` with the snapshot at `path`, or store it there when
+/// `update` is set
+pub fn check_snapshot(path: &Path, actual: &str, update: bool) -> Result<(), String> {
+    if update {
+        std::fs::create_dir_all(path.parent().unwrap()).map_err(|e| e.to_string())?;
+        return std::fs::write(path, actual).map_err(|e| format!("Could not write {}: {}", path.display(), e));
+    }
+
+    let expected = std::fs::read_to_string(path).map_err(|_| {
+        format!("No snapshot at {}, run with {}=1 to create it", path.display(), UPDATE_ENV)
+    })?;
+    if expected == actual {
+        return Ok(());
+    }
+
+    Err(format!(
+        "{} does not match, run with {}=1 if the change is intended\n{}",
+        path.display(),
+        UPDATE_ENV,
+        first_difference(&expected, actual)
+    ))
+}
+
+/// Describe the first line where two texts differ. Tabs are shown as `\t`
+/// so that indentation changes are visible
+fn first_difference(expected: &str, actual: &str) -> String {
+    let show = |line: Option<&str>| match line {
+        Some(line) => format!("{:?}", line),
+        None => "end of report".to_string(),
+    };
+
+    let mut expected_lines = expected.split('\n');
+    let mut actual_lines = actual.split('\n');
+    for line_num in 1.. {
+        let (e, a) = (expected_lines.next(), actual_lines.next());
+        if e != a {
+            return format!("line {}:\n  expected {}\n  actual   {}", line_num, show(e), show(a));
+        }
+    }
+    unreachable!()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days_of_advent;
+
+    #[test]
+    fn every_day_matches_its_snapshots() {
+        let update = update_mode();
+        let mut failures = vec![];
+
+        for puzzle in days_of_advent::puzzles() {
+            let inputs_dir = io::share_dir(&puzzle.name()).join("inputs");
+            let inputs = match io::load_inputs_from_dir(&inputs_dir) {
+                Ok(inputs) if !inputs.is_empty() => inputs,
+                _ => {
+                    failures.push(format!("{} has no inputs in {} to snapshot", puzzle.name(), inputs_dir.display()));
+                    continue;
+                }
+            };
+            for input in inputs {
+                let snapshots = match render_snapshots(puzzle.as_ref(), &input) {
+                    Ok(snapshots) => snapshots,
+                    Err(e) => {
+                        failures.push(e);
+                        continue;
+                    }
+                };
+                for (file_name, actual) in snapshots {
+                    let path = snapshot_dir(puzzle.as_ref()).join(file_name);
+                    if let Err(e) = check_snapshot(&path, &actual, update) {
+                        failures.push(e);
+                    }
+                }
+            }
+        }
+
+        assert!(failures.is_empty(), "\n{}", failures.join("\n\n"));
+    }
+
+    #[test]
+    fn check_snapshot_points_at_first_difference() {
+        let dir = std::env::temp_dir().join(format!("aoc-snapshot-{}", std::process::id()));
+        let path = dir.join("example.report");
+
+        let missing = check_snapshot(&path, "Day 1: A\n\tB\n", false);
+        check_snapshot(&path, "Day 1: A\n\tB\n", true).unwrap();
+        let matching = check_snapshot(&path, "Day 1: A\n\tB\n", false);
+        let changed = check_snapshot(&path, "Day 1: A\n    B\n", false);
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert!(missing.unwrap_err().contains("UPDATE_SNAPSHOTS=1"));
+        assert_eq!(matching, Ok(()));
+        assert!(changed.unwrap_err().ends_with("line 2:\n  expected \"\\tB\"\n  actual   \"    B\""));
+    }
+}
@@ -0,0 +1,88 @@
+use std::fmt::{Debug, Display};
+use std::hash::Hash;
+use std::str::FromStr;
+
+/// An integer type that expense report entries can be read as. Only checked
+/// arithmetic is offered, so that overflow is always an error
+pub trait Entry: Copy + Ord + Hash + Debug + Display + FromStr {
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn checked_add(self, other: Self) -> Option<Self>;
+    fn checked_sub(self, other: Self) -> Option<Self>;
+    fn checked_mul(self, other: Self) -> Option<Self>;
+
+    /// Every entry type fits in an `i128`, so tables of sums are indexed
+    /// in that type
+    fn to_i128(self) -> i128;
+
+    fn type_name() -> &'static str;
+}
+
+macro_rules! impl_entry {
+    ($($t:ident),*) => {
+        $(
+            impl Entry for $t {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+
+                fn checked_add(self, other: Self) -> Option<Self> {
+                    $t::checked_add(self, other)
+                }
+
+                fn checked_sub(self, other: Self) -> Option<Self> {
+                    $t::checked_sub(self, other)
+                }
+
+                fn checked_mul(self, other: Self) -> Option<Self> {
+                    $t::checked_mul(self, other)
+                }
+
+                fn to_i128(self) -> i128 {
+                    self as i128
+                }
+
+                fn type_name() -> &'static str {
+                    stringify!($t)
+                }
+            }
+        )*
+    };
+}
+
+impl_entry!(i32, i64, u64, i128);
+
+/// Add up entries, failing rather than overflowing
+pub fn checked_sum<T: Entry>(entries: &[T]) -> Result<T, String> {
+    entries.iter().try_fold(T::ZERO, |total, e| total.checked_add(*e)).ok_or_else(|| {
+        format!("The sum of {} overflows {}", join(entries, " + "), T::type_name())
+    })
+}
+
+/// Multiply entries, failing rather than overflowing
+pub fn checked_product<T: Entry>(entries: &[T]) -> Result<T, String> {
+    entries.iter().try_fold(T::ONE, |total, e| total.checked_mul(*e)).ok_or_else(|| {
+        format!("The product of {} overflows {}", join(entries, " * "), T::type_name())
+    })
+}
+
+pub fn join<T: Entry>(entries: &[T], separator: &str) -> String {
+    entries.iter().map(|e| e.to_string()).collect::<Vec<String>>().join(separator)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overflow_is_an_error() {
+        assert_eq!(checked_product(&[1000i32, 1000, 1000]), Ok(1_000_000_000));
+        assert_eq!(
+            checked_product(&[2000i32, 2000, 2000]),
+            Err("The product of 2000 * 2000 * 2000 overflows i32".to_string())
+        );
+        assert_eq!(checked_product(&[2000i64, 2000, 2000]), Ok(8_000_000_000));
+        assert_eq!(checked_sum(&[u64::MAX, 1]), Err(format!("The sum of {} + 1 overflows u64", u64::MAX)));
+        assert_eq!(checked_sum::<i128>(&[]), Ok(0));
+    }
+}
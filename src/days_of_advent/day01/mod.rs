@@ -0,0 +1,592 @@
+use std::collections::HashMap;
+
+use crate::days_of_advent::common::io::PuzzleInput;
+use crate::days_of_advent::common::puzzle::{Answer, Puzzle};
+
+mod entry;
+mod nearest;
+mod subset;
+
+pub use entry::Entry;
+pub use nearest::{nearest_addends, Bound};
+pub use subset::{report_subsets, Witnesses};
+use entry::{checked_product, checked_sum, join};
+
+/// The Day 01 puzzle, finding entries that sum to a given value and
+/// multiplying those entries
+pub struct RepairReport;
+
+impl Puzzle for RepairReport {
+    fn day(&self) -> usize {
+        1
+    }
+
+    fn title(&self) -> &'static str {
+        "Repair Report"
+    }
+
+    fn description(&self) -> &'static str {
+        "Find entries that add to 2020, and multiply them"
+    }
+
+    fn version(&self) -> &'static str {
+        "6"
+    }
+
+    fn solve(&self, input: &str) -> Result<Vec<Answer>, String> {
+        let report = parse_entries::<i64>(input)?;
+
+        const SUM : i64 = 2020;
+
+        let result_2 = repair_report(&report, SUM, 2)?;
+        let result_3 = repair_report(&report, SUM, 3)?;
+
+        let (product_2, product_3) = (result_2.product()?, result_3.product()?);
+
+        Ok(vec![
+            Answer::new(product_2, format!("Found two addends that make {}, and they multiply to {}", SUM, product_2))
+                .explained_by(explain_with_ambiguity(&result_2, &report, SUM)?),
+            Answer::new(product_3, format!("Found three addends that make {}, and they multiply to {}", SUM, product_3))
+                .explained_by(explain_with_ambiguity(&result_3, &report, SUM)?),
+        ])
+    }
+}
+
+/// How the last two entries of a k-sum are found
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Strategy {
+    /// Sort the entries once, then close in on each pair from both ends
+    TwoPointer,
+    /// Look each complement up among the entries already passed
+    HashSet,
+}
+
+/// The entries of an expense report, with the line each was read from
+/// counting from 1
+#[derive(Debug, PartialEq)]
+pub struct ExpenseReport<T> {
+    pub entries: Vec<T>,
+    pub lines: Vec<usize>,
+}
+
+/// The entries chosen from the expense report, with the index of each one
+/// among the report's entries counting from 0. `ExpenseReport::lines` gives
+/// the line each was read from
+#[derive(Debug, PartialEq)]
+pub struct Repair<T> {
+    pub addends: Vec<T>,
+    pub indices: Vec<usize>,
+}
+
+impl<T: Entry> Repair<T> {
+    /// Order the chosen entries by the line they were found on
+    fn from_chosen(mut chosen: Vec<(usize, T)>) -> Self {
+        chosen.sort_unstable();
+        Repair {
+            addends: chosen.iter().map(|(_, a)| *a).collect(),
+            indices: chosen.iter().map(|(idx, _)| *idx).collect(),
+        }
+    }
+
+    /// Multiply the entries, or fail if the product does not fit in `T`
+    pub fn product(&self) -> Result<T, String> {
+        checked_product(&self.addends)
+    }
+
+    fn explain(&self, lines: &[usize]) -> Result<Vec<String>, String> {
+        Ok(vec![
+            format!("Entries on lines {}", describe_lines(lines, &self.indices)),
+            format!("{} = {}", join(&self.addends, " + "), checked_sum(&self.addends)?),
+            format!("{} = {}", join(&self.addends, " * "), self.product()?),
+        ])
+    }
+}
+
+/// Explain a repair, warning when other entries would have summed to `sum`
+/// too and so given a different product
+fn explain_with_ambiguity<T: Entry>(
+    repair: &Repair<T>,
+    report: &ExpenseReport<T>,
+    sum: T,
+) -> Result<Vec<String>, String> {
+    let mut explanation = repair.explain(&report.lines)?;
+    let num_solutions = count_addends(&report.entries, sum, repair.addends.len());
+    if num_solutions > 1 {
+        explanation.push(format!("Ambiguous: {} sets of {} entries sum to {}", num_solutions, repair.addends.len(), sum));
+    }
+    Ok(explanation)
+}
+
+/// Read one entry per line as a `T`, skipping blank lines, and naming the
+/// line of any entry that is not a number or does not fit
+pub fn parse_entries<T: Entry>(entries: &str) -> Result<ExpenseReport<T>, String>
+where
+    T::Err: std::fmt::Display,
+{
+    let numbers = PuzzleInput::new(entries)
+        .numbers::<T>()
+        .map_err(|e| format!("{}, reading entries as {}", e, T::type_name()))?;
+
+    Ok(ExpenseReport {
+        lines: numbers.iter().map(|(line, _)| *line).collect(),
+        entries: numbers.into_iter().map(|(_, entry)| entry).collect(),
+    })
+}
+
+/// Find the addends, or say how close the nearest entries came
+fn repair_report<T: Entry>(report: &ExpenseReport<T>, sum: T, num_to_sum: usize) -> Result<Repair<T>, String> {
+    find_addends(&report.entries, sum, num_to_sum, Strategy::HashSet).ok_or_else(|| {
+        let missing = format!("No {} addends in input summed to {}", num_to_sum, sum);
+        match nearest_addends(&report.entries, sum, num_to_sum, Bound::Any) {
+            Some(nearest) => format!("{}, the nearest were {}", missing, nearest.describe(sum, &report.lines)),
+            None => missing,
+        }
+    })
+}
+
+/// Find `k` entries on distinct lines that add up to `sum`. When several
+/// sets of entries would do, the strategies may choose different ones
+pub fn find_addends<T: Entry>(entries: &[T], sum: T, k: usize, strategy: Strategy) -> Option<Repair<T>> {
+    let mut indexed: Vec<(usize, T)> = entries.iter().copied().enumerate().collect();
+    if strategy == Strategy::TwoPointer {
+        indexed.sort_unstable_by_key(|(idx, entry)| (*entry, *idx));
+    }
+
+    let chosen = k_sum(&indexed, sum, k, strategy)?;
+    Some(Repair::from_chosen(chosen))
+}
+
+/// Choose `k` of the (line, entry) pairs that add up to `sum`, fixing one
+/// entry at a time until only a pair is left to find. Complements that do
+/// not fit in `T` cannot be made from its entries, so are skipped
+fn k_sum<T: Entry>(entries: &[(usize, T)], sum: T, k: usize, strategy: Strategy) -> Option<Vec<(usize, T)>> {
+    match k {
+        0 if sum == T::ZERO => Some(vec![]),
+        0 => None,
+        1 => entries.iter().find(|(_, entry)| *entry == sum).map(|chosen| vec![*chosen]),
+        2 => match strategy {
+            Strategy::TwoPointer => pair_sum_two_pointer(entries, sum),
+            Strategy::HashSet => pair_sum_hash_set(entries, sum),
+        }
+        .map(|(first, second)| vec![first, second]),
+        _ => entries.iter().enumerate().find_map(|(pos, first)| {
+            let complement = sum.checked_sub(first.1)?;
+            let mut chosen = k_sum(&entries[pos + 1..], complement, k - 1, strategy)?;
+            chosen.push(*first);
+            Some(chosen)
+        }),
+    }
+}
+
+/// Every set of `k` entries on distinct lines that adds up to `sum`, in
+/// order of their lines
+pub fn all_addends<T: Entry>(entries: &[T], sum: T, k: usize) -> Vec<Repair<T>> {
+    let indexed: Vec<(usize, T)> = entries.iter().copied().enumerate().collect();
+
+    let mut repairs: Vec<Repair<T>> = all_k_sums(&indexed, sum, k).into_iter().map(Repair::from_chosen).collect();
+    repairs.sort_unstable_by(|a, b| a.indices.cmp(&b.indices));
+    repairs
+}
+
+fn all_k_sums<T: Entry>(entries: &[(usize, T)], sum: T, k: usize) -> Vec<Vec<(usize, T)>> {
+    match k {
+        0 if sum == T::ZERO => vec![vec![]],
+        0 => vec![],
+        2 => {
+            let mut pairs = vec![];
+            let mut seen: HashMap<T, Vec<(usize, T)>> = HashMap::new();
+            for entry in entries {
+                if let Some(earlier) = sum.checked_sub(entry.1).and_then(|c| seen.get(&c)) {
+                    pairs.extend(earlier.iter().map(|e| vec![*e, *entry]));
+                }
+                seen.entry(entry.1).or_default().push(*entry);
+            }
+            pairs
+        }
+        _ => {
+            let mut chosen = vec![];
+            for (pos, first) in entries.iter().enumerate() {
+                if let Some(complement) = sum.checked_sub(first.1) {
+                    for mut rest in all_k_sums(&entries[pos + 1..], complement, k - 1) {
+                        rest.push(*first);
+                        chosen.push(rest);
+                    }
+                }
+            }
+            chosen
+        }
+    }
+}
+
+/// Count the sets of `k` entries on distinct lines that add up to `sum`,
+/// without building any of them
+pub fn count_addends<T: Entry>(entries: &[T], sum: T, k: usize) -> usize {
+    match k {
+        0 => (sum == T::ZERO) as usize,
+        1 => entries.iter().filter(|e| **e == sum).count(),
+        2 => {
+            let mut num_pairs = 0;
+            let mut seen: HashMap<T, usize> = HashMap::with_capacity(entries.len());
+            for entry in entries {
+                num_pairs += sum.checked_sub(*entry).and_then(|c| seen.get(&c)).unwrap_or(&0);
+                *seen.entry(*entry).or_insert(0) += 1;
+            }
+            num_pairs
+        }
+        _ => entries
+            .iter()
+            .enumerate()
+            .filter_map(|(pos, first)| Some(count_addends(&entries[pos + 1..], sum.checked_sub(*first)?, k - 1)))
+            .sum(),
+    }
+}
+
+/// The only set of `k` entries that adds up to `sum`, or an error when there
+/// is none or the answer would depend on which set was chosen
+pub fn unique_addends<T: Entry>(report: &ExpenseReport<T>, sum: T, k: usize) -> Result<Repair<T>, String> {
+    let mut repairs = all_addends(&report.entries, sum, k);
+    match repairs.len() {
+        1 => Ok(repairs.remove(0)),
+        0 => Err(format!("No set of {} entries sums to {}", k, sum)),
+        num_solutions => {
+            let lines: Vec<String> = repairs.iter().map(|r| describe_lines(&report.lines, &r.indices)).collect();
+            Err(format!(
+                "Ambiguous: {} sets of {} entries sum to {}, on lines {}",
+                num_solutions,
+                k,
+                sum,
+                lines.join("; ")
+            ))
+        }
+    }
+}
+
+/// What the `addends` command reports about the sets of entries
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AddendsMode {
+    /// List every set of entries
+    List,
+    /// Only count the sets
+    Count,
+    /// Give the product, failing unless exactly one set exists
+    Unique,
+    /// Give the set whose sum comes closest, even if none is exact
+    Nearest(Bound),
+}
+
+/// The integer type the `addends` command reads entries as
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EntryWidth {
+    I32,
+    I64,
+    U64,
+    I128,
+}
+
+impl std::str::FromStr for EntryWidth {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        match s {
+            "i32" => Ok(EntryWidth::I32),
+            "i64" => Ok(EntryWidth::I64),
+            "u64" => Ok(EntryWidth::U64),
+            "i128" => Ok(EntryWidth::I128),
+            _ => Err(format!("'{}' is not one of i32, i64, u64 or i128", s)),
+        }
+    }
+}
+
+/// Explore the sets of `k` entries in an expense report that add up to `sum`,
+/// reading the entries and sum as `width` integers
+pub fn report_addends(input: &str, sum: &str, k: usize, mode: AddendsMode, width: EntryWidth) -> Result<String, String> {
+    match width {
+        EntryWidth::I32 => report_addends_as::<i32>(input, sum, k, mode),
+        EntryWidth::I64 => report_addends_as::<i64>(input, sum, k, mode),
+        EntryWidth::U64 => report_addends_as::<u64>(input, sum, k, mode),
+        EntryWidth::I128 => report_addends_as::<i128>(input, sum, k, mode),
+    }
+}
+
+fn report_addends_as<T: Entry>(input: &str, sum: &str, k: usize, mode: AddendsMode) -> Result<String, String>
+where
+    T::Err: std::fmt::Display,
+{
+    let report = parse_entries::<T>(input)?;
+    let sum = parse_sum::<T>(sum)?;
+
+    match mode {
+        AddendsMode::Count => Ok(describe_count(count_addends(&report.entries, sum, k), k, sum)),
+        AddendsMode::Unique => {
+            let repair = unique_addends(&report, sum, k)?;
+            Ok(format!("{}\n{}", repair.product()?, repair.explain(&report.lines)?.join("\n")))
+        }
+        AddendsMode::Nearest(bound) => nearest_addends(&report.entries, sum, k, bound)
+            .map(|nearest| nearest.describe(sum, &report.lines))
+            .ok_or_else(|| match bound {
+                Bound::Any => format!("Fewer than {} entries to add up", k),
+                _ => format!("No set of {} entries sums {} {}", k, describe_bound(bound), sum),
+            }),
+        AddendsMode::List => {
+            let repairs = all_addends(&report.entries, sum, k);
+            let mut lines = vec![describe_count(repairs.len(), k, sum)];
+            for r in &repairs {
+                lines.push(format!(
+                    "Lines {}: {} multiply to {}",
+                    describe_lines(&report.lines, &r.indices),
+                    join(&r.addends, " * "),
+                    r.product()?
+                ));
+            }
+            Ok(lines.join("\n"))
+        }
+    }
+}
+
+fn parse_sum<T: Entry>(sum: &str) -> Result<T, String>
+where
+    T::Err: std::fmt::Display,
+{
+    sum.parse::<T>()
+        .map_err(|e| format!("Sum '{}' is not a number ({}), reading entries as {}", sum, e, T::type_name()))
+}
+
+fn describe_bound(bound: Bound) -> &'static str {
+    match bound {
+        Bound::Any => "to about",
+        Bound::AtMost => "to at most",
+        Bound::AtLeast => "to at least",
+    }
+}
+
+fn describe_count<T: Entry>(num_solutions: usize, k: usize, sum: T) -> String {
+    match num_solutions {
+        1 => format!("1 set of {} entries sums to {}", k, sum),
+        _ => format!("{} sets of {} entries sum to {}", num_solutions, k, sum),
+    }
+}
+
+/// The lines the chosen entries were read from
+fn describe_lines(lines: &[usize], indices: &[usize]) -> String {
+    indices.iter().map(|idx| lines[*idx].to_string()).collect::<Vec<String>>().join(", ")
+}
+
+/// Find a pair in entries sorted by value, moving in from whichever end
+/// brings the total closer to `sum`. A total too big for `T` is more than
+/// `sum` when the larger entry is positive, and less otherwise
+fn pair_sum_two_pointer<T: Entry>(entries: &[(usize, T)], sum: T) -> Option<((usize, T), (usize, T))> {
+    if entries.len() < 2 {
+        return None;
+    }
+    let (mut lo, mut hi) = (0, entries.len() - 1);
+    while lo < hi {
+        let ordering = match entries[lo].1.checked_add(entries[hi].1) {
+            Some(total) => total.cmp(&sum),
+            None if entries[hi].1 > T::ZERO => std::cmp::Ordering::Greater,
+            None => std::cmp::Ordering::Less,
+        };
+        match ordering {
+            std::cmp::Ordering::Equal => return Some((entries[lo], entries[hi])),
+            std::cmp::Ordering::Less => lo += 1,
+            std::cmp::Ordering::Greater => hi -= 1,
+        }
+    }
+    None
+}
+
+/// Find a pair by looking up each entry's complement among the entries
+/// before it, so an entry is never paired with itself
+fn pair_sum_hash_set<T: Entry>(entries: &[(usize, T)], sum: T) -> Option<((usize, T), (usize, T))> {
+    let mut seen: HashMap<T, (usize, T)> = HashMap::with_capacity(entries.len());
+    for entry in entries {
+        if let Some(complement) = sum.checked_sub(entry.1) {
+            if let Some(earlier) = seen.get(&complement) {
+                return Some((*earlier, *entry));
+            }
+        }
+        seen.entry(entry.1).or_insert(*entry);
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ExpenseReport;
+
+    /// A report with one entry on each line
+    fn report<T>(entries: Vec<T>) -> ExpenseReport<T> {
+        ExpenseReport { lines: (1..=entries.len()).collect(), entries }
+    }
+
+    #[test]
+    fn test_parse_entries() {
+        let entries = "1\n-1\n0\n11\n99\n2147483647\r\n";
+        let solution = vec![1, -1, 0, 11, 99, 2_147_483_647];
+
+        assert_eq!(super::parse_entries::<i32>(entries), Ok(report(solution)));
+        assert_eq!(
+            super::parse_entries::<i32>("1\n2147483648\n"),
+            Err("Line 2: '2147483648' is not a number (number too large to fit in target type), reading entries as i32"
+                .to_string())
+        );
+        assert_eq!(super::parse_entries::<i64>("1\n2147483648\n"), Ok(report(vec![1, 2_147_483_648])));
+        assert!(super::parse_entries::<u64>("1\n-1\n").is_err());
+    }
+
+    #[test]
+    fn entries_keep_their_lines() {
+        use super::{report_addends, AddendsMode, EntryWidth};
+
+        let parsed = super::parse_entries::<i64>("1721\n\n979\n366\n\n299\n").unwrap();
+        assert_eq!(parsed.entries, [1721, 979, 366, 299]);
+        assert_eq!(parsed.lines, [1, 3, 4, 6]);
+        assert_eq!(
+            report_addends("1721\n\n979\n366\n\n299\n", "2020", 2, AddendsMode::Unique, EntryWidth::I64),
+            Ok("514579\nEntries on lines 1, 6\n1721 + 299 = 2020\n1721 * 299 = 514579".to_string())
+        );
+        assert_eq!(
+            super::parse_entries::<i64>("1721 979\n"),
+            Err("Line 1: expected one number, found '1721 979', reading entries as i64".to_string())
+        );
+    }
+
+    #[test]
+    fn wide_entries_never_overflow_silently() {
+        use super::{find_addends, report_addends, AddendsMode, EntryWidth, Strategy};
+
+        let products = "2000\n2000\n2000\n";
+        assert_eq!(
+            report_addends(products, "6000", 3, AddendsMode::Unique, EntryWidth::I32),
+            Err("The product of 2000 * 2000 * 2000 overflows i32".to_string())
+        );
+        assert!(report_addends(products, "6000", 3, AddendsMode::Unique, EntryWidth::I64)
+            .unwrap()
+            .starts_with("8000000000\n"));
+
+        let big = [i64::MAX, 1, i64::MAX - 1, 2];
+        for strategy in [Strategy::HashSet, Strategy::TwoPointer] {
+            assert_eq!(find_addends(&big, i64::MAX, 2, strategy).unwrap().indices, [1, 2]);
+            assert_eq!(find_addends(&big, i64::MIN, 2, strategy), None);
+        }
+
+        let huge = u64::MAX.to_string();
+        let input = format!("{}\n0\n", huge);
+        assert_eq!(
+            report_addends(&input, &huge, 2, AddendsMode::Count, EntryWidth::U64).unwrap(),
+            format!("1 set of 2 entries sums to {}", huge)
+        );
+        assert!(report_addends("1\n", "1e3", 1, AddendsMode::Count, EntryWidth::I128).is_err());
+    }
+
+    #[test]
+    fn provided_acceptance_test() {
+        let input = [1721, 979, 366, 299, 675, 1456];
+        let solution = 514579;
+
+        let proposed_solution = super::repair_report(&report(input.to_vec()), 2020, 2).unwrap();
+
+        assert_eq!(proposed_solution.product(), Ok(solution));
+        assert_eq!(proposed_solution.addends, [1721, 299]);
+        assert_eq!(proposed_solution.indices, [0, 3]);
+    }
+
+    #[test]
+    fn provided_acceptance_test_3() {
+        let input = [1721, 979, 366, 299, 675, 1456];
+
+        let proposed_solution = super::repair_report(&report(input.to_vec()), 2020, 3).unwrap();
+
+        assert_eq!(proposed_solution.product(), Ok(241861950));
+        assert_eq!(proposed_solution.addends, [979, 366, 675]);
+        let explanation = proposed_solution.explain(&[1, 2, 3, 4, 5, 6]).unwrap();
+        assert_eq!(explanation[0], "Entries on lines 2, 3, 5");
+        assert_eq!(explanation[1], "979 + 366 + 675 = 2020");
+    }
+
+    #[test]
+    fn entries_are_never_reused() {
+        use super::{find_addends, Strategy};
+
+        for strategy in [Strategy::HashSet, Strategy::TwoPointer] {
+            assert_eq!(find_addends(&[1010, 5, 7], 2020, 2, strategy), None);
+            let repair = find_addends(&[1010, 5, 1010], 2020, 2, strategy).unwrap();
+            assert_eq!(repair.indices, [0, 2]);
+        }
+    }
+
+    #[test]
+    fn strategies_agree_for_any_k() {
+        use super::{find_addends, Strategy};
+        let input = [1721, 979, 366, 299, 675, 1456];
+
+        for k in 1..=4 {
+            for sum in [299, 2020, 2319, 2796, 3000] {
+                let hash_set = find_addends(&input, sum, k, Strategy::HashSet);
+                let two_pointer = find_addends(&input, sum, k, Strategy::TwoPointer);
+                assert_eq!(hash_set.is_some(), two_pointer.is_some(), "k = {}, sum = {}", k, sum);
+                if let Some(repair) = hash_set {
+                    assert_eq!(repair.addends.iter().sum::<i32>(), sum);
+                    assert_eq!(repair.addends.len(), k);
+                }
+            }
+        }
+        assert_eq!(find_addends(&input, 2796, 4, Strategy::TwoPointer).unwrap().indices, [2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn enumerate_and_count_every_solution() {
+        use super::{all_addends, count_addends};
+        let input = [1721, 979, 366, 299, 675, 1456, 1010, 1010, 1010];
+
+        let pairs = all_addends(&input, 2020, 2);
+        let pair_indices: Vec<Vec<usize>> = pairs.iter().map(|r| r.indices.clone()).collect();
+
+        assert_eq!(pair_indices, [vec![0, 3], vec![6, 7], vec![6, 8], vec![7, 8]]);
+        for k in 0..=4 {
+            for sum in [0, 1010, 2020, 3030] {
+                assert_eq!(count_addends(&input, sum, k), all_addends(&input, sum, k).len(), "k = {}, sum = {}", k, sum);
+            }
+        }
+    }
+
+    #[test]
+    fn unique_mode_flags_ambiguous_input() {
+        use super::{report_addends, unique_addends, AddendsMode, EntryWidth};
+        let input = [1721, 979, 366, 299, 675, 1456];
+
+        assert_eq!(unique_addends(&report(input.to_vec()), 2020, 3).unwrap().product(), Ok(241861950));
+        assert_eq!(
+            unique_addends(&report(vec![1010, 1010, 1721, 299]), 2020, 2),
+            Err("Ambiguous: 2 sets of 2 entries sum to 2020, on lines 1, 2; 3, 4".to_string())
+        );
+        assert!(unique_addends(&report(input.to_vec()), 1, 2).is_err());
+        assert_eq!(
+            report_addends("1010\n1010\n1721\n299\n", "2020", 2, AddendsMode::List, EntryWidth::I64).unwrap(),
+            "2 sets of 2 entries sum to 2020\nLines 1, 2: 1010 * 1010 multiply to 1020100\nLines 3, 4: 1721 * 299 multiply to 514579"
+        );
+        assert_eq!(
+            report_addends("1010\n1010\n1721\n299\n", "2020", 2, AddendsMode::Count, EntryWidth::I32).unwrap(),
+            "2 sets of 2 entries sum to 2020"
+        );
+    }
+
+    #[test]
+    fn missing_addends_point_at_the_nearest() {
+        use super::{repair_report, report_addends, AddendsMode, Bound, EntryWidth};
+
+        assert_eq!(
+            repair_report(&report(vec![1721, 979, 366, 299]), 2000, 2),
+            Err("No 2 addends in input summed to 2000, the nearest were Lines 1, 4: 1721 + 299 = 2020, 20 away from 2000"
+                .to_string())
+        );
+        assert_eq!(repair_report(&report(vec![2020]), 2020, 2), Err("No 2 addends in input summed to 2020".to_string()));
+        assert_eq!(
+            report_addends("1721\n979\n366\n299\n", "2000", 2, AddendsMode::Nearest(Bound::AtMost), EntryWidth::I64),
+            Ok("Lines 2, 3: 979 + 366 = 1345, 655 away from 2000".to_string())
+        );
+        assert_eq!(
+            report_addends("721\n979\n", "2000", 2, AddendsMode::Nearest(Bound::AtLeast), EntryWidth::I64),
+            Err("No set of 2 entries sums to at least 2000".to_string())
+        );
+    }
+}
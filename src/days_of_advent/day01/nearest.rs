@@ -0,0 +1,193 @@
+use super::entry::join;
+use super::{describe_lines, Entry, Repair};
+
+/// Which side of the target a nearest sum may fall on
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Bound {
+    /// Either side
+    Any,
+    /// No more than the target
+    AtMost,
+    /// No less than the target
+    AtLeast,
+}
+
+impl Bound {
+    fn allows(self, sum: i128, target: i128) -> bool {
+        match self {
+            Bound::Any => true,
+            Bound::AtMost => sum <= target,
+            Bound::AtLeast => sum >= target,
+        }
+    }
+}
+
+/// The entries whose sum came closest to a target. Sums are kept as `i128`
+/// because the sum of several entries need not fit in their own type
+#[derive(Debug, PartialEq)]
+pub struct Nearest<T> {
+    pub repair: Repair<T>,
+    pub sum: i128,
+    pub distance: u128,
+}
+
+impl<T: Entry> Nearest<T> {
+    /// Describe the entries by the `lines` they were read from
+    pub fn describe(&self, target: T, lines: &[usize]) -> String {
+        format!(
+            "Lines {}: {} = {}, {} away from {}",
+            describe_lines(lines, &self.repair.indices),
+            join(&self.repair.addends, " + "),
+            self.sum,
+            self.distance,
+            target
+        )
+    }
+}
+
+/// The closest (sum, chosen entries) found so far
+type Best = Option<(i128, Vec<(usize, i128)>)>;
+
+/// Find `k` entries on distinct lines whose sum is as close to `target` as
+/// `bound` allows. `None` when there are fewer than `k` entries, or no set
+/// falls on the allowed side of the target. Of several equally close sets,
+/// any may be chosen
+pub fn nearest_addends<T: Entry>(entries: &[T], target: T, k: usize, bound: Bound) -> Option<Nearest<T>> {
+    let mut sorted: Vec<(usize, i128)> = entries.iter().map(|e| e.to_i128()).enumerate().collect();
+    sorted.sort_unstable_by_key(|(idx, value)| (*value, *idx));
+
+    let target = target.to_i128();
+    let (sum, chosen) = nearest(&sorted, target, 0, k, bound)?;
+    Some(Nearest {
+        repair: Repair::from_chosen(chosen.into_iter().map(|(idx, _)| (idx, entries[idx])).collect()),
+        sum,
+        distance: sum.abs_diff(target),
+    })
+}
+
+/// The closest sum of `k` sorted entries plus `base`, fixing one entry at a
+/// time until a pair is left to close in on from both ends. Sums too big for
+/// an `i128` are passed over
+fn nearest(sorted: &[(usize, i128)], target: i128, base: i128, k: usize, bound: Bound) -> Best {
+    let mut best: Best = None;
+    let mut consider = |sum: i128, chosen: Vec<(usize, i128)>| {
+        if bound.allows(sum, target) && best.as_ref().is_none_or(|(b, _)| sum.abs_diff(target) < b.abs_diff(target)) {
+            best = Some((sum, chosen));
+        }
+    };
+
+    match k {
+        0 => consider(base, vec![]),
+        1 => {
+            for entry in sorted {
+                if let Some(sum) = base.checked_add(entry.1) {
+                    consider(sum, vec![*entry]);
+                }
+            }
+        }
+        2 if sorted.len() >= 2 => {
+            let (mut lo, mut hi) = (0, sorted.len() - 1);
+            while lo < hi {
+                let sum = base.checked_add(sorted[lo].1).and_then(|s| s.checked_add(sorted[hi].1));
+                let sum = match sum {
+                    Some(sum) => sum,
+                    None if sorted[hi].1 > 0 => {
+                        hi -= 1;
+                        continue;
+                    }
+                    None => {
+                        lo += 1;
+                        continue;
+                    }
+                };
+                consider(sum, vec![sorted[lo], sorted[hi]]);
+                match sum.cmp(&target) {
+                    std::cmp::Ordering::Less => lo += 1,
+                    std::cmp::Ordering::Greater => hi -= 1,
+                    std::cmp::Ordering::Equal => break,
+                }
+            }
+        }
+        2 => {}
+        _ => {
+            for (pos, first) in sorted.iter().enumerate() {
+                // Fixing an entry equal to the last one finds nothing new
+                if pos > 0 && sorted[pos - 1].1 == first.1 {
+                    continue;
+                }
+                let found = base
+                    .checked_add(first.1)
+                    .and_then(|base| nearest(&sorted[pos + 1..], target, base, k - 1, bound));
+                if let Some((sum, mut chosen)) = found {
+                    chosen.push(*first);
+                    consider(sum, chosen);
+                }
+            }
+        }
+    }
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The distance of the closest allowed sum, checking every set of `k`
+    fn brute_force(entries: &[i64], target: i64, k: usize, bound: Bound) -> Option<u128> {
+        let n = entries.len();
+        (0u32..1 << n)
+            .filter(|mask| mask.count_ones() as usize == k)
+            .map(|mask| (0..n).filter(|i| mask & (1 << i) != 0).map(|i| entries[i] as i128).sum::<i128>())
+            .filter(|sum| bound.allows(*sum, target as i128))
+            .map(|sum| sum.abs_diff(target as i128))
+            .min()
+    }
+
+    #[test]
+    fn closest_sum_when_none_is_exact() {
+        let input = [1721i64, 979, 366, 299, 675, 1456];
+
+        let exact = nearest_addends(&input, 2020, 2, Bound::Any).unwrap();
+        let below = nearest_addends(&input, 2000, 2, Bound::AtMost).unwrap();
+        let above = nearest_addends(&input, 2000, 2, Bound::AtLeast).unwrap();
+
+        assert_eq!((exact.sum, exact.distance, exact.repair.indices), (2020, 0, vec![0, 3]));
+        assert_eq!((below.sum, below.distance), (1822, 178));
+        assert_eq!((above.sum, above.distance), (2020, 20));
+        assert_eq!(above.describe(2000, &[1, 2, 3, 5, 6, 7]), "Lines 1, 5: 1721 + 299 = 2020, 20 away from 2000");
+        assert_eq!(nearest_addends(&input, 0, 2, Bound::AtMost), None);
+        assert_eq!(nearest_addends(&input, 0, 7, Bound::Any), None);
+    }
+
+    #[test]
+    fn agrees_with_brute_force() {
+        let input = [3i64, -4, 7, 7, 0, 12, -1, 5, 9, -6, 2];
+
+        for k in 0..=4 {
+            for target in -25..=40 {
+                for bound in [Bound::Any, Bound::AtMost, Bound::AtLeast] {
+                    let nearest = nearest_addends(&input, target, k, bound);
+                    assert_eq!(
+                        nearest.as_ref().map(|n| n.distance),
+                        brute_force(&input, target, k, bound),
+                        "k = {}, target = {}, {:?}",
+                        k,
+                        target,
+                        bound
+                    );
+                    if let Some(n) = nearest {
+                        assert_eq!(n.repair.addends.iter().map(|a| *a as i128).sum::<i128>(), n.sum);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn sums_wider_than_the_entries() {
+        let nearest = nearest_addends(&[i32::MAX, i32::MAX, 1], 0, 2, Bound::Any).unwrap();
+
+        assert_eq!(nearest.sum, i32::MAX as i128 + 1);
+        assert_eq!(nearest.distance, i32::MAX as u128 + 1);
+    }
+}
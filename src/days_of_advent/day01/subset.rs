@@ -0,0 +1,417 @@
+use std::collections::HashMap;
+
+use super::entry::join;
+use super::{describe_lines, parse_entries, parse_sum, Entry, EntryWidth, Repair};
+
+/// Tables of reachable sums with more cells than this are not built, and the
+/// entries are split in half instead. Each cell takes one bit
+const MAX_TABLE_CELLS: usize = 1 << 28;
+
+/// Splitting in half lists every subset of each half, so gives up beyond
+/// this many entries
+const MAX_SPLIT_ENTRIES: usize = 40;
+
+/// How many subsets to reconstruct
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Witnesses {
+    /// The smallest subset, choosing the earliest lines among those
+    One,
+    /// Every subset, in order of their lines
+    All,
+}
+
+/// How the subsets are searched for
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SubsetAlgorithm {
+    /// Tabulate which sums each size of subset can reach
+    Table,
+    /// List the sums of each half of the entries, then match them up
+    MeetInTheMiddle,
+}
+
+/// The subsets to look for: those with between `min_size` and `max_size`
+/// entries, on distinct lines, that add up to `sum`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SubsetQuery<T> {
+    pub sum: T,
+    pub min_size: usize,
+    pub max_size: usize,
+    pub witnesses: Witnesses,
+}
+
+impl<T: Entry> SubsetQuery<T> {
+    /// Look for one non-empty subset of any size
+    pub fn new(sum: T) -> Self {
+        SubsetQuery {
+            sum,
+            min_size: 1,
+            max_size: usize::MAX,
+            witnesses: Witnesses::One,
+        }
+    }
+}
+
+/// Tabulate when the table would be small enough, and split the entries in
+/// half otherwise
+pub fn choose_algorithm<T: Entry>(entries: &[T], query: &SubsetQuery<T>) -> SubsetAlgorithm {
+    match SumTable::cells(entries, query.sum, query.max_size.min(entries.len())) {
+        Some(cells) if cells <= MAX_TABLE_CELLS => SubsetAlgorithm::Table,
+        _ => SubsetAlgorithm::MeetInTheMiddle,
+    }
+}
+
+/// Find the subsets of entries that answer `query`
+pub fn subset_sum<T: Entry>(entries: &[T], query: &SubsetQuery<T>) -> Result<Vec<Repair<T>>, String> {
+    subset_sum_with(entries, query, choose_algorithm(entries, query))
+}
+
+/// Find the subsets of entries that answer `query` with a given algorithm.
+/// Both algorithms give the same subsets
+pub fn subset_sum_with<T: Entry>(
+    entries: &[T],
+    query: &SubsetQuery<T>,
+    algorithm: SubsetAlgorithm,
+) -> Result<Vec<Repair<T>>, String> {
+    let max_size = query.max_size.min(entries.len());
+    if query.min_size > max_size {
+        return Ok(vec![]);
+    }
+
+    let chosen = match algorithm {
+        SubsetAlgorithm::Table => SumTable::build(entries, query.sum, max_size)?.witnesses(query, max_size),
+        SubsetAlgorithm::MeetInTheMiddle => meet_in_the_middle(entries, query, max_size)?,
+    };
+
+    let mut repairs: Vec<Repair<T>> = chosen
+        .into_iter()
+        .map(|indices| Repair::from_chosen(indices.into_iter().map(|idx| (idx, entries[idx])).collect()))
+        .collect();
+    repairs.sort_unstable_by(|a, b| a.indices.cmp(&b.indices));
+    Ok(repairs)
+}
+
+/// Which sums each number of the entries from each position onwards can
+/// reach, stored flat as bits of `[position][size][sum - min_sum]`
+struct SumTable {
+    values: Vec<i128>,
+    min_sum: i128,
+    width: usize,
+    max_size: usize,
+    reachable: Vec<u64>,
+}
+
+impl SumTable {
+    /// The lowest and highest sums worth tabulating, if they fit. When no
+    /// entry is negative, sums past `target` can never come back down to
+    /// it, and likewise when no entry is positive
+    fn bounds<T: Entry>(entries: &[T], target: T) -> Option<(i128, i128)> {
+        let (lo, hi) = entries.iter().try_fold((0i128, 0i128), |(lo, hi), e| match e.to_i128() {
+            v if v < 0 => Some((lo.checked_add(v)?, hi)),
+            v => Some((lo, hi.checked_add(v)?)),
+        })?;
+        let target = target.to_i128();
+        match (lo, hi) {
+            (0, _) => Some((0, hi.min(target.max(0)))),
+            (_, 0) => Some((lo.max(target.min(0)), 0)),
+            _ => Some((lo, hi)),
+        }
+    }
+
+    fn cells<T: Entry>(entries: &[T], target: T, max_size: usize) -> Option<usize> {
+        let (lo, hi) = Self::bounds(entries, target)?;
+        let width = hi.checked_sub(lo)?.checked_add(1)?;
+        if width > usize::MAX as i128 {
+            return None;
+        }
+        (entries.len() + 1).checked_mul(max_size + 1)?.checked_mul(width as usize)
+    }
+
+    fn build<T: Entry>(entries: &[T], target: T, max_size: usize) -> Result<Self, String> {
+        let cells = Self::cells(entries, target, max_size)
+            .filter(|cells| *cells <= MAX_TABLE_CELLS)
+            .ok_or("The sums of these entries are too spread out to tabulate")?;
+        let (min_sum, max_sum) = Self::bounds(entries, target).unwrap();
+
+        let mut table = SumTable {
+            values: entries.iter().map(|e| e.to_i128()).collect(),
+            min_sum,
+            width: (max_sum - min_sum + 1) as usize,
+            max_size,
+            reachable: vec![0; cells.div_ceil(64)],
+        };
+
+        let n = entries.len();
+        table.set(table.at(n, 0, 0).unwrap());
+        for pos in (0..n).rev() {
+            for size in 0..=max_size {
+                for offset in 0..table.width {
+                    let sum = min_sum + offset as i128;
+                    let skip = table.can_reach(pos + 1, size, sum);
+                    let take = size > 0 && table.can_reach(pos + 1, size - 1, sum - table.values[pos]);
+                    if skip || take {
+                        table.set(table.at(pos, size, sum).unwrap());
+                    }
+                }
+            }
+        }
+        Ok(table)
+    }
+
+    fn at(&self, pos: usize, size: usize, sum: i128) -> Option<usize> {
+        let offset = sum.checked_sub(self.min_sum)?;
+        if offset < 0 || offset >= self.width as i128 {
+            return None;
+        }
+        Some((pos * (self.max_size + 1) + size) * self.width + offset as usize)
+    }
+
+    fn set(&mut self, cell: usize) {
+        self.reachable[cell / 64] |= 1 << (cell % 64);
+    }
+
+    fn can_reach(&self, pos: usize, size: usize, sum: i128) -> bool {
+        self.at(pos, size, sum).is_some_and(|cell| self.reachable[cell / 64] & (1 << (cell % 64)) != 0)
+    }
+
+    /// Walk back through the table from the smallest size that reaches the
+    /// sum, choosing each entry before skipping it so that subsets come out
+    /// earliest lines first
+    fn witnesses<T: Entry>(&self, query: &SubsetQuery<T>, max_size: usize) -> Vec<Vec<usize>> {
+        let mut found = vec![];
+        for size in query.min_size..=max_size {
+            if self.can_reach(0, size, query.sum.to_i128()) {
+                self.walk(0, size, query.sum.to_i128(), &mut vec![], &mut found, query.witnesses);
+            }
+            if query.witnesses == Witnesses::One && !found.is_empty() {
+                break;
+            }
+        }
+        found
+    }
+
+    fn walk(
+        &self,
+        pos: usize,
+        size: usize,
+        sum: i128,
+        chosen: &mut Vec<usize>,
+        found: &mut Vec<Vec<usize>>,
+        witnesses: Witnesses,
+    ) {
+        if size == 0 && sum == 0 {
+            found.push(chosen.clone());
+            return;
+        }
+        if size > 0 && self.can_reach(pos + 1, size - 1, sum - self.values[pos]) {
+            chosen.push(pos);
+            self.walk(pos + 1, size - 1, sum - self.values[pos], chosen, found, witnesses);
+            chosen.pop();
+        }
+        if witnesses == Witnesses::One && !found.is_empty() {
+            return;
+        }
+        if self.can_reach(pos + 1, size, sum) {
+            self.walk(pos + 1, size, sum, chosen, found, witnesses);
+        }
+    }
+}
+
+/// Every subset of `half` with at most `max_size` entries, as its sum, its
+/// size and a bit mask of the entries in it
+fn half_sums(half: &[i128], max_size: usize) -> Result<Vec<(i128, usize, u64)>, String> {
+    let mut sums = vec![0i128; 1 << half.len()];
+    for mask in 1..sums.len() {
+        let lowest = mask.trailing_zeros() as usize;
+        sums[mask] = sums[mask & (mask - 1)]
+            .checked_add(half[lowest])
+            .ok_or("The sums of these entries overflow i128")?;
+    }
+    Ok(sums
+        .into_iter()
+        .enumerate()
+        .map(|(mask, sum)| (sum, (mask as u64).count_ones() as usize, mask as u64))
+        .filter(|(_, size, _)| *size <= max_size)
+        .collect())
+}
+
+fn meet_in_the_middle<T: Entry>(entries: &[T], query: &SubsetQuery<T>, max_size: usize) -> Result<Vec<Vec<usize>>, String> {
+    if entries.len() > MAX_SPLIT_ENTRIES {
+        return Err(format!(
+            "Too many entries to search: {} entries with sums this spread out, but at most {} can be split in half",
+            entries.len(),
+            MAX_SPLIT_ENTRIES
+        ));
+    }
+
+    let values: Vec<i128> = entries.iter().map(|e| e.to_i128()).collect();
+    let (left, right) = values.split_at(values.len() / 2);
+
+    let mut right_by_sum: HashMap<i128, Vec<(usize, u64)>> = HashMap::new();
+    for (sum, size, mask) in half_sums(right, max_size)? {
+        right_by_sum.entry(sum).or_default().push((size, mask));
+    }
+
+    let indices = |mask: u64, first: usize| (0..64).filter(move |bit| mask & (1 << bit) != 0).map(move |bit| first + bit);
+
+    let target = query.sum.to_i128();
+    let mut found: Vec<Vec<usize>> = vec![];
+    for (left_sum, left_size, left_mask) in half_sums(left, max_size)? {
+        let matches = target.checked_sub(left_sum).and_then(|rest| right_by_sum.get(&rest));
+        for (right_size, right_mask) in matches.into_iter().flatten() {
+            let size = left_size + right_size;
+            if size < query.min_size || size > max_size {
+                continue;
+            }
+            let chosen: Vec<usize> = indices(left_mask, 0).chain(indices(*right_mask, left.len())).collect();
+            match query.witnesses {
+                Witnesses::All => found.push(chosen),
+                Witnesses::One => {
+                    let better = found.first().is_none_or(|best| (best.len(), best) > (chosen.len(), &chosen));
+                    if better {
+                        found = vec![chosen];
+                    }
+                }
+            }
+        }
+    }
+    Ok(found)
+}
+
+/// Search an expense report for subsets adding up to `sum`, reading the
+/// entries and sum as `width` integers
+pub fn report_subsets(
+    input: &str,
+    sum: &str,
+    min_size: usize,
+    max_size: Option<usize>,
+    witnesses: Witnesses,
+    width: EntryWidth,
+) -> Result<String, String> {
+    match width {
+        EntryWidth::I32 => report_subsets_as::<i32>(input, sum, min_size, max_size, witnesses),
+        EntryWidth::I64 => report_subsets_as::<i64>(input, sum, min_size, max_size, witnesses),
+        EntryWidth::U64 => report_subsets_as::<u64>(input, sum, min_size, max_size, witnesses),
+        EntryWidth::I128 => report_subsets_as::<i128>(input, sum, min_size, max_size, witnesses),
+    }
+}
+
+fn report_subsets_as<T: Entry>(
+    input: &str,
+    sum: &str,
+    min_size: usize,
+    max_size: Option<usize>,
+    witnesses: Witnesses,
+) -> Result<String, String>
+where
+    T::Err: std::fmt::Display,
+{
+    let report = parse_entries::<T>(input)?;
+    let query = SubsetQuery {
+        min_size,
+        max_size: max_size.unwrap_or(usize::MAX),
+        witnesses,
+        ..SubsetQuery::new(parse_sum::<T>(sum)?)
+    };
+    let sizes = describe_sizes(min_size, query.max_size.min(report.entries.len()));
+
+    let repairs = subset_sum(&report.entries, &query)?;
+    let describe = |r: &Repair<T>| {
+        format!("Lines {}: {}", describe_lines(&report.lines, &r.indices), join(&r.addends, " + "))
+    };
+    match witnesses {
+        Witnesses::One => repairs
+            .first()
+            .map(|r| format!("{} = {}", describe(r), query.sum))
+            .ok_or_else(|| format!("No set of {} entries sums to {}", sizes, query.sum)),
+        Witnesses::All => {
+            let mut lines = vec![match repairs.len() {
+                1 => format!("1 set of {} entries sums to {}", sizes, query.sum),
+                n => format!("{} sets of {} entries sum to {}", n, sizes, query.sum),
+            }];
+            lines.extend(repairs.iter().map(describe));
+            Ok(lines.join("\n"))
+        }
+    }
+}
+
+fn describe_sizes(min_size: usize, max_size: usize) -> String {
+    match min_size.cmp(&max_size) {
+        std::cmp::Ordering::Less => format!("{} to {}", min_size, max_size),
+        _ => min_size.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: [i64; 6] = [1721, 979, 366, 299, 675, 1456];
+
+    fn lines(repairs: &[Repair<i64>]) -> Vec<Vec<usize>> {
+        repairs.iter().map(|r| r.indices.clone()).collect()
+    }
+
+    #[test]
+    fn finds_subsets_of_any_size() {
+        let one = subset_sum(&EXAMPLE, &SubsetQuery::new(2020)).unwrap();
+        let all = subset_sum(&EXAMPLE, &SubsetQuery { witnesses: Witnesses::All, ..SubsetQuery::new(2020) }).unwrap();
+        let three = SubsetQuery { min_size: 3, max_size: 3, ..SubsetQuery::new(2020) };
+
+        assert_eq!(lines(&one), [vec![0, 3]]);
+        assert_eq!(lines(&all), [vec![0, 3], vec![1, 2, 4]]);
+        assert_eq!(lines(&subset_sum(&EXAMPLE, &three).unwrap()), [vec![1, 2, 4]]);
+        assert!(subset_sum(&EXAMPLE, &SubsetQuery::new(1)).unwrap().is_empty());
+        assert_eq!(
+            lines(&subset_sum(&EXAMPLE, &SubsetQuery { min_size: 0, ..SubsetQuery::new(0) }).unwrap()),
+            [Vec::<usize>::new()]
+        );
+    }
+
+    #[test]
+    fn algorithms_agree() {
+        let mixed: Vec<i64> = vec![3, -4, 7, 7, 0, 12, -1, 5, 9, -6, 2];
+        let positive: Vec<i64> = mixed.iter().map(|e| e.abs()).collect();
+        let negative: Vec<i64> = positive.iter().map(|e| -e).collect();
+
+        for (entries, sum) in [mixed, positive, negative].iter().flat_map(|e| (-30..=30).map(move |sum| (e, sum))) {
+            for (min_size, max_size) in [(1, usize::MAX), (2, 3), (4, 4), (0, 2)] {
+                for witnesses in [Witnesses::One, Witnesses::All] {
+                    let query = SubsetQuery { sum, min_size, max_size, witnesses };
+                    let table = subset_sum_with(entries, &query, SubsetAlgorithm::Table).unwrap();
+                    let split = subset_sum_with(entries, &query, SubsetAlgorithm::MeetInTheMiddle).unwrap();
+
+                    assert_eq!(table, split, "{:?}", query);
+                    for r in &table {
+                        assert_eq!(r.addends.iter().sum::<i64>(), sum);
+                        assert!(r.addends.len() >= min_size && r.addends.len() <= max_size);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn large_targets_are_split_in_half() {
+        let entries: Vec<u64> = (0..30).map(|i| 1 << (i + 20)).collect();
+        let query = SubsetQuery::new((1 << 20) + (1 << 49));
+
+        assert_eq!(choose_algorithm(&entries, &query), SubsetAlgorithm::MeetInTheMiddle);
+        let report: Vec<i64> = (1..=200).map(|i| i * 10).collect();
+        assert_eq!(choose_algorithm(&report, &SubsetQuery::new(2020)), SubsetAlgorithm::Table);
+        assert_eq!(subset_sum(&entries, &query).unwrap()[0].indices, [0, 29]);
+        assert!(subset_sum(&[u64::MAX; 41], &query).is_err());
+    }
+
+    #[test]
+    fn report_lists_subsets() {
+        assert_eq!(
+            report_subsets("1721\n979\n366\n299\n675\n1456\n", "2020", 1, None, Witnesses::All, EntryWidth::I64),
+            Ok("2 sets of 1 to 6 entries sum to 2020\nLines 1, 4: 1721 + 299\nLines 2, 3, 5: 979 + 366 + 675"
+                .to_string())
+        );
+        assert_eq!(
+            report_subsets("1\n2\n", "4", 1, Some(2), Witnesses::One, EntryWidth::I32),
+            Err("No set of 1 to 2 entries sums to 4".to_string())
+        );
+    }
+}
@@ -0,0 +1,282 @@
+use std::collections::{BTreeMap, HashMap};
+
+use super::{parse_password_file, BytePasswordPolicyParser, IndexPasswordPolicy, LinePolicy, RangePasswordPolicy};
+use crate::days_of_advent::common::io::PuzzleInput;
+use crate::days_of_advent::day02::policies::validates_password::ValidatesPassword;
+use crate::days_of_advent::day02::policies::violation::Violation;
+
+/// How many times each key was seen, in key order
+type Histogram<K> = BTreeMap<K, usize>;
+
+/// The widest bar drawn in a histogram
+const BAR_WIDTH: usize = 40;
+
+/// The most line numbers listed for lines passing only one policy
+const LISTED_LINES: usize = 10;
+
+/// Aggregate statistics over the lines of a password file
+#[derive(Debug, Default, PartialEq)]
+struct Audit {
+    lines: usize,
+    rejected: usize,
+    /// Lines read as range policies but not as index policies
+    index_rejected: usize,
+    characters: Histogram<char>,
+    /// The smallest and largest first and second numbers
+    first_numbers: Option<(usize, usize)>,
+    second_numbers: Option<(usize, usize)>,
+    /// How many counts each range allows beyond its minimum
+    widths: Histogram<usize>,
+    /// Ranges whose minimum is above their maximum, which no password meets
+    impossible_ranges: usize,
+    lengths: Histogram<usize>,
+    range_valid: usize,
+    index_valid: usize,
+    range_only: Vec<usize>,
+    index_only: Vec<usize>,
+    /// How many edits each failing password is from passing, or `None` when
+    /// that cannot be counted
+    range_edits: Histogram<Option<usize>>,
+    index_edits: Histogram<Option<usize>>,
+}
+
+impl Audit {
+    fn new(
+        range_policies: &[(usize, &str, LinePolicy<RangePasswordPolicy>)],
+        index_policies: &[(usize, &str, LinePolicy<IndexPasswordPolicy>)],
+        rejected: usize,
+    ) -> Audit {
+        let mut audit = Audit {
+            lines: range_policies.len(),
+            rejected,
+            index_rejected: range_policies.len() - index_policies.len(),
+            ..Audit::default()
+        };
+        let index_by_line: HashMap<usize, &LinePolicy<IndexPasswordPolicy>> =
+            index_policies.iter().map(|(line, _, policy)| (*line, policy)).collect();
+
+        for (line, password, range_policy) in range_policies {
+            let range = &range_policy.policy.range;
+            *audit.characters.entry(range_policy.policy.character).or_default() += 1;
+            audit.first_numbers = Some(extend(audit.first_numbers, *range.start()));
+            audit.second_numbers = Some(extend(audit.second_numbers, *range.end()));
+            match range.end().checked_sub(*range.start()) {
+                Some(width) => *audit.widths.entry(width).or_default() += 1,
+                None => audit.impossible_ranges += 1,
+            }
+            *audit.lengths.entry(password.chars().count()).or_default() += 1;
+
+            let range_valid = tally(&mut audit.range_edits, range_policy, password);
+            audit.range_valid += range_valid as usize;
+
+            if let Some(index_policy) = index_by_line.get(line) {
+                let index_valid = tally(&mut audit.index_edits, *index_policy, password);
+                audit.index_valid += index_valid as usize;
+                match (range_valid, index_valid) {
+                    (true, false) => audit.range_only.push(*line),
+                    (false, true) => audit.index_only.push(*line),
+                    _ => {}
+                }
+            }
+        }
+        audit
+    }
+
+    fn render(&self) -> String {
+        let mut report = vec![format!("Audited {} password lines", self.lines)];
+        if self.rejected > 0 {
+            report.push(format!("{} could not be read", count_lines(self.rejected)));
+        }
+
+        report.push(String::new());
+        report.extend(render_histogram("Policy characters", rows(&self.characters)));
+        if let (Some(first), Some(second)) = (self.first_numbers, self.second_numbers) {
+            report.push(format!("First numbers run from {} to {}", first.0, first.1));
+            report.push(format!("Second numbers run from {} to {}", second.0, second.1));
+        }
+        report.extend(render_histogram("Range widths (second number minus first)", rows(&self.widths)));
+        if self.impossible_ranges > 0 {
+            report.push(format!("  {} ranges have a first number above their second", self.impossible_ranges));
+        }
+        report.extend(render_histogram("Password lengths", rows(&self.lengths)));
+
+        report.push(String::new());
+        let index_lines = self.lines - self.index_rejected;
+        report.push(format!("Range policy: {}", proportion(self.range_valid, self.lines)));
+        report.push(format!("Index policy: {}", proportion(self.index_valid, index_lines)));
+        if self.index_rejected > 0 {
+            report.push(format!("  {} without valid index positions", count_lines(self.index_rejected)));
+        }
+        report.push(format!("Range policy only: {}", describe_lines(&self.range_only)));
+        report.push(format!("Index policy only: {}", describe_lines(&self.index_only)));
+
+        report.extend(render_histogram("Edits to pass the range policy", edit_labels(&self.range_edits)));
+        report.extend(render_histogram("Edits to pass the index policy", edit_labels(&self.index_edits)));
+        report.join("\n")
+    }
+}
+
+/// Audit a password file: the distribution of its policy characters, ranges
+/// and password lengths, how many passwords pass each policy and only one of
+/// them, and how far failing passwords are from passing
+pub fn report_audit(input: &str) -> Result<String, String> {
+    let input = PuzzleInput::new(input);
+
+    let (range_policies, rejected) =
+        parse_password_file::<LinePolicy<RangePasswordPolicy>>(&input, &BytePasswordPolicyParser);
+    let (index_policies, _) = parse_password_file::<LinePolicy<IndexPasswordPolicy>>(&input, &BytePasswordPolicyParser);
+    if range_policies.is_empty() {
+        return Err("No password lines could be read to audit".to_string());
+    }
+
+    Ok(Audit::new(&range_policies, &index_policies, rejected.len()).render())
+}
+
+/// Record how many edits the password is from passing the policy, if it
+/// fails, and say whether it passes
+fn tally(edits: &mut Histogram<Option<usize>>, policy: &impl ValidatesPassword, password: &str) -> bool {
+    let violations = policy.violations(password);
+    if violations.is_empty() {
+        return true;
+    }
+    let needed = violations.iter().map(Violation::edits).sum::<Option<usize>>();
+    *edits.entry(needed).or_default() += 1;
+    false
+}
+
+fn extend(bounds: Option<(usize, usize)>, value: usize) -> (usize, usize) {
+    match bounds {
+        Some((low, high)) => (low.min(value), high.max(value)),
+        None => (value, value),
+    }
+}
+
+fn count_lines(n: usize) -> String {
+    match n {
+        1 => "1 line".to_string(),
+        n => format!("{} lines", n),
+    }
+}
+
+fn proportion(count: usize, total: usize) -> String {
+    match total {
+        0 => "no lines".to_string(),
+        _ => format!("{} of {} pass ({:.1}%)", count, total, 100.0 * count as f64 / total as f64),
+    }
+}
+
+fn describe_lines(lines: &[usize]) -> String {
+    let listed: Vec<String> = lines.iter().take(LISTED_LINES).map(|line| line.to_string()).collect();
+    match lines.len() {
+        0 => "none".to_string(),
+        1 => format!("1 (line {})", listed[0]),
+        n if n > LISTED_LINES => format!("{} (lines {}, ...)", n, listed.join(", ")),
+        n => format!("{} (lines {})", n, listed.join(", ")),
+    }
+}
+
+fn rows<K: ToString>(histogram: &Histogram<K>) -> Vec<(String, usize)> {
+    histogram.iter().map(|(key, count)| (key.to_string(), *count)).collect()
+}
+
+/// Label edit counts for rendering, with the uncountable ones last
+fn edit_labels(edits: &Histogram<Option<usize>>) -> Vec<(String, usize)> {
+    let mut labels: Vec<(String, usize)> = edits
+        .iter()
+        .filter_map(|(needed, count)| needed.map(|needed| (needed.to_string(), *count)))
+        .collect();
+    if let Some(count) = edits.get(&None) {
+        labels.push(("other".to_string(), *count));
+    }
+    labels
+}
+
+/// A title, then a line for each key with its count and a bar scaled to the
+/// largest count. Nothing when there is nothing to show
+fn render_histogram(title: &str, rows: Vec<(String, usize)>) -> Vec<String> {
+    let largest = match rows.iter().map(|(_, count)| *count).max() {
+        Some(largest) => largest,
+        None => return vec![],
+    };
+    let key_width = rows.iter().map(|(key, _)| key.chars().count()).max().unwrap_or(0);
+    let count_width = largest.to_string().len();
+
+    let mut lines = vec![format!("{}:", title)];
+    for (key, count) in rows {
+        let bar = (count * BAR_WIDTH).div_ceil(largest);
+        lines.push(format!("  {:>kw$} {:>cw$} {}", key, count, "#".repeat(bar), kw = key_width, cw = count_width));
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "1-3 a: abcde\n1-3 b: cdefg\n2-9 c: ccccccccc\n0-2 a: aa\nbad line\n";
+
+    #[test]
+    fn audit_example() {
+        let input = PuzzleInput::new(INPUT);
+        let (range_policies, rejected) =
+            parse_password_file::<LinePolicy<RangePasswordPolicy>>(&input, &BytePasswordPolicyParser);
+        let (index_policies, _) =
+            parse_password_file::<LinePolicy<IndexPasswordPolicy>>(&input, &BytePasswordPolicyParser);
+
+        let audit = Audit::new(&range_policies, &index_policies, rejected.len());
+
+        assert_eq!((audit.lines, audit.rejected, audit.index_rejected), (4, 1, 1));
+        assert_eq!(audit.characters, [('a', 2), ('b', 1), ('c', 1)].iter().copied().collect());
+        assert_eq!((audit.first_numbers, audit.second_numbers), (Some((0, 2)), Some((2, 9))));
+        assert_eq!(audit.widths, [(2, 3), (7, 1)].iter().copied().collect());
+        assert_eq!(audit.lengths, [(2, 1), (5, 2), (9, 1)].iter().copied().collect());
+        assert_eq!((audit.range_valid, audit.index_valid), (3, 1));
+        assert_eq!((audit.range_only.as_slice(), audit.index_only.as_slice()), (&[3][..], &[][..]));
+        assert_eq!(audit.range_edits, [(Some(1), 1)].iter().copied().collect());
+        assert_eq!(audit.index_edits, [(Some(1), 2)].iter().copied().collect());
+    }
+
+    #[test]
+    fn list_a_few_lines() {
+        assert_eq!(describe_lines(&[]), "none");
+        assert_eq!(describe_lines(&[4, 9]), "2 (lines 4, 9)");
+        assert_eq!(describe_lines(&(1..=12).collect::<Vec<_>>()), "12 (lines 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, ...)");
+    }
+
+    #[test]
+    fn render_audit() {
+        let report = report_audit(INPUT).unwrap();
+
+        let expected = [
+            "Audited 4 password lines",
+            "1 line could not be read",
+            "",
+            "Policy characters:",
+            "  a 2 ########################################",
+            "  b 1 ####################",
+            "  c 1 ####################",
+            "First numbers run from 0 to 2",
+            "Second numbers run from 2 to 9",
+            "Range widths (second number minus first):",
+            "  2 3 ########################################",
+            "  7 1 ##############",
+            "Password lengths:",
+            "  2 1 ####################",
+            "  5 2 ########################################",
+            "  9 1 ####################",
+            "",
+            "Range policy: 3 of 4 pass (75.0%)",
+            "Index policy: 1 of 3 pass (33.3%)",
+            "  1 line without valid index positions",
+            "Range policy only: 1 (line 3)",
+            "Index policy only: none",
+            "Edits to pass the range policy:",
+            "  1 1 ########################################",
+            "Edits to pass the index policy:",
+            "  1 2 ########################################",
+        ];
+
+        assert_eq!(report, expected.join("\n"));
+        assert!(report_audit("bad line\n").is_err());
+    }
+}
\ No newline at end of file
@@ -0,0 +1,352 @@
+use super::{
+    BytePasswordPolicyParser, PasswordLine, PolicyRecord, PolicyRecordParser, PolicyRule, Positions, RangePasswordPolicy,
+};
+
+/// How many random policies to try for a line before giving up on the rule
+const POLICY_ATTEMPTS: usize = 1000;
+
+/// A small seeded generator, so the same seed always gives the same
+/// passwords
+struct Rng(u64);
+
+impl Rng {
+    /// A number below `n`, which must not be 0
+    fn below(&mut self, n: usize) -> usize {
+        self.0 = self.0.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        ((self.0 >> 33) % n as u64) as usize
+    }
+
+    fn choose<T: Copy>(&mut self, items: &[T]) -> T {
+        items[self.below(items.len())]
+    }
+
+    fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+}
+
+/// What a rule can tell about a password of a line: how many times the
+/// line's character appears, and whether it is at each of the line's
+/// positions that fall within the password
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Shape {
+    count: usize,
+    first: Option<bool>,
+    second: Option<bool>,
+}
+
+/// Makes passwords of a fixed length from an alphabet, that pass or fail a
+/// rule over the range and index policies of a line. Since those policies
+/// only look at how often the line's character appears and where, every
+/// such shape of password is tried against the rule, and passwords are
+/// drawn at random from the shapes that give the wanted result
+pub struct PasswordGenerator {
+    length: usize,
+    alphabet: Vec<char>,
+    rng: Rng,
+}
+
+impl PasswordGenerator {
+    /// The line's character is used wherever a policy needs it, whether or
+    /// not it is in `alphabet`. Every other character comes from `alphabet`
+    pub fn new(length: usize, alphabet: &str, seed: u64) -> Result<PasswordGenerator, String> {
+        let mut letters: Vec<char> = vec![];
+        for c in alphabet.chars() {
+            if !letters.contains(&c) {
+                letters.push(c);
+            }
+        }
+        if letters.is_empty() {
+            return Err("The alphabet needs at least one character".to_string());
+        }
+        Ok(PasswordGenerator { length, alphabet: letters, rng: Rng(seed) })
+    }
+
+    /// A password that passes the rule for the line's policy when `valid`,
+    /// or fails it otherwise
+    pub fn generate(&mut self, rule: &PolicyRule, record: &PolicyRecord<'_>, valid: bool) -> Result<String, String> {
+        if !record.extras.is_empty() {
+            return Err("Passwords cannot be generated for extra policies such as 'length 8-20'".to_string());
+        }
+        let policy = rule.policy(record, Positions::default()).map_err(|_| "Positions count from 1".to_string())?;
+
+        let shapes: Vec<Shape> = self
+            .shapes(record)
+            .into_iter()
+            .filter(|shape| policy.is_password_valid(&self.fill(record, *shape, false)) == valid)
+            .collect();
+        if shapes.is_empty() {
+            return Err(format!(
+                "No {}-character password of '{}' can {} the rule {} for the policy {}",
+                self.length,
+                self.alphabet.iter().collect::<String>(),
+                if valid { "pass" } else { "fail" },
+                rule,
+                line_policy(record)
+            ));
+        }
+
+        let shape = self.rng.choose(&shapes);
+        let password = self.fill(record, shape, true);
+        debug_assert_eq!(policy.is_password_valid(&password), valid);
+        Ok(password)
+    }
+
+    /// A password file of `count` lines, exactly `valid` of which pass the
+    /// rule. Every line has `policy` if given, or else a policy chosen at
+    /// random that can give the line's result
+    pub fn generate_file(
+        &mut self,
+        rule: &PolicyRule,
+        policy: Option<&PolicyRecord<'_>>,
+        count: usize,
+        valid: usize,
+    ) -> Result<String, String> {
+        if valid > count {
+            return Err(format!("Cannot make {} valid passwords in {} lines", valid, count));
+        }
+        let mut results: Vec<bool> = (0..count).map(|idx| idx < valid).collect();
+        self.rng.shuffle(&mut results);
+
+        let mut file = String::new();
+        for valid in results {
+            let (record, password) = match policy {
+                Some(record) => (clone_record(record), self.generate(rule, record, valid)?),
+                None => self.generate_random_policy(rule, valid)?,
+            };
+            file += &PasswordLine { policy: &line_policy(&record), password: &password }.to_string();
+            file.push('\n');
+        }
+        Ok(file)
+    }
+
+    fn generate_random_policy(
+        &mut self,
+        rule: &PolicyRule,
+        valid: bool,
+    ) -> Result<(PolicyRecord<'static>, String), String> {
+        if self.length == 0 {
+            return Err("Random policies need passwords of at least 1 character".to_string());
+        }
+        for _ in 0..POLICY_ATTEMPTS {
+            let first = 1 + self.rng.below(self.length);
+            let second = first + self.rng.below(self.length - first + 1);
+            let character = self.rng.choose(&self.alphabet);
+            let record = PolicyRecord { numbers: (first, second), character, extras: vec![], password: "" };
+            if let Ok(password) = self.generate(rule, &record, valid) {
+                return Ok((record, password));
+            }
+        }
+        Err(format!(
+            "No random policy let a {}-character password {} the rule {}",
+            self.length,
+            if valid { "pass" } else { "fail" },
+            rule
+        ))
+    }
+
+    /// Every shape a password of this length can take for the line
+    fn shapes(&self, record: &PolicyRecord<'_>) -> Vec<Shape> {
+        let (first, second) = record.numbers;
+        let has_fillers = !self.fillers(record.character).is_empty();
+        let options = |position: usize| match (position >= 1 && position <= self.length, has_fillers) {
+            (false, _) => vec![None],
+            (true, true) => vec![Some(false), Some(true)],
+            (true, false) => vec![Some(true)],
+        };
+
+        let mut shapes = vec![];
+        for first_holds in options(first) {
+            for second_holds in options(second) {
+                if first == second && first_holds != second_holds {
+                    continue;
+                }
+                let (fixed, held) = self.fixed_positions(record, first_holds, second_holds);
+                let others = self.length - fixed;
+                // Without other characters, every free position must hold the character
+                let fewest = if has_fillers { 0 } else { others };
+                for extra in fewest..=others {
+                    shapes.push(Shape { count: held + extra, first: first_holds, second: second_holds });
+                }
+            }
+        }
+        shapes
+    }
+
+    /// How many distinct positions of the line fall within the password, and
+    /// how many of those hold the character
+    fn fixed_positions(&self, record: &PolicyRecord<'_>, first: Option<bool>, second: Option<bool>) -> (usize, usize) {
+        let mut positions = vec![];
+        if let Some(holds) = first {
+            positions.push((record.numbers.0, holds));
+        }
+        if let Some(holds) = second {
+            if record.numbers.1 != record.numbers.0 {
+                positions.push((record.numbers.1, holds));
+            }
+        }
+        (positions.len(), positions.iter().filter(|(_, holds)| *holds).count())
+    }
+
+    fn fillers(&self, character: char) -> Vec<char> {
+        self.alphabet.iter().copied().filter(|c| *c != character).collect()
+    }
+
+    /// A password of the given shape. With `random`, the character's other
+    /// places and the other characters are chosen at random, and otherwise
+    /// the first free places and the first other character are used
+    fn fill(&mut self, record: &PolicyRecord<'_>, shape: Shape, random: bool) -> String {
+        let character = record.character;
+        let fillers = self.fillers(character);
+        let mut password: Vec<Option<char>> = vec![None; self.length];
+
+        let mut held = 0;
+        for (position, holds) in [(record.numbers.0, shape.first), (record.numbers.1, shape.second)].iter() {
+            if let Some(holds) = holds {
+                let slot = &mut password[position - 1];
+                if slot.is_none() {
+                    held += *holds as usize;
+                }
+                *slot = Some(if *holds { character } else { fillers[0] });
+            }
+        }
+
+        let mut free: Vec<usize> = (0..self.length).filter(|idx| password[*idx].is_none()).collect();
+        if random {
+            self.rng.shuffle(&mut free);
+        }
+        for (nth, idx) in free.into_iter().enumerate() {
+            password[idx] = Some(match (nth < shape.count - held, random) {
+                (true, _) => character,
+                (false, true) => self.rng.choose(&fillers),
+                (false, false) => fillers[0],
+            });
+        }
+
+        // Positions holding another character were given the first one
+        if random {
+            for (position, holds) in [(record.numbers.0, shape.first), (record.numbers.1, shape.second)].iter() {
+                if *holds == Some(false) {
+                    password[position - 1] = Some(self.rng.choose(&fillers));
+                }
+            }
+        }
+        password.into_iter().map(|c| c.unwrap_or(character)).collect()
+    }
+}
+
+/// Read a policy such as `1-3 a`
+pub fn parse_policy(policy: &str) -> Result<PolicyRecord<'static>, String> {
+    let line = format!("{}: ", policy);
+    let record = BytePasswordPolicyParser.parse_record(&line).map_err(|e| e.to_string())?;
+    Ok(clone_record(&record))
+}
+
+fn clone_record(record: &PolicyRecord<'_>) -> PolicyRecord<'static> {
+    PolicyRecord { numbers: record.numbers, character: record.character, extras: record.extras.clone(), password: "" }
+}
+
+/// The line's policy, as it is written
+fn line_policy(record: &PolicyRecord<'_>) -> RangePasswordPolicy {
+    RangePasswordPolicy {
+        range: std::ops::RangeInclusive::new(record.numbers.0, record.numbers.1),
+        character: record.character,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days_of_advent::day02::report_rule;
+
+    const RULES: [&str; 5] = ["range", "index", "range and index", "range xor index", "not (range or index)"];
+
+    #[test]
+    fn generated_passwords_have_the_wanted_result() {
+        let policies = ["1-3 a", "2-9 c", "3-3 b", "1-1 a", "4-12 z", "0-0 q"];
+
+        for seed in 0..20 {
+            let mut generator = PasswordGenerator::new(10, "abcz", seed).unwrap();
+            for rule in RULES.iter() {
+                let rule: PolicyRule = rule.parse().unwrap();
+                for policy in policies.iter() {
+                    let record = parse_policy(policy).unwrap();
+                    let checker = rule.policy(&record, Positions::default());
+                    for valid in [true, false] {
+                        match (generator.generate(&rule, &record, valid), &checker) {
+                            (Ok(password), Ok(checker)) => {
+                                assert_eq!(password.chars().count(), 10);
+                                let result = checker.is_password_valid(&password);
+                                assert_eq!(result, valid, "{} {} {}", rule, policy, password);
+                            }
+                            (Err(_), _) => {}
+                            (Ok(password), Err(_)) => {
+                                panic!("{} has no valid positions, but made {}", policy, password)
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn same_seed_same_passwords() {
+        let rule: PolicyRule = "range and not index".parse().unwrap();
+        let record = parse_policy("2-5 a").unwrap();
+        let generate = |seed: u64| {
+            let mut generator = PasswordGenerator::new(12, "abcdefgh", seed).unwrap();
+            (0..5).map(|_| generator.generate(&rule, &record, true).unwrap()).collect::<Vec<_>>()
+        };
+
+        assert_eq!(generate(7), generate(7));
+        assert_ne!(generate(7), generate(8));
+    }
+
+    #[test]
+    fn report_unsatisfiable_policies() {
+        let mut generator = PasswordGenerator::new(4, "ab", 1).unwrap();
+        let range: PolicyRule = "range".parse().unwrap();
+
+        assert_eq!(
+            generator.generate(&range, &parse_policy("5-9 a").unwrap(), true),
+            Err("No 4-character password of 'ab' can pass the rule range for the policy 5-9 a".to_string())
+        );
+        assert_eq!(
+            generator.generate(&"range and not range".parse().unwrap(), &parse_policy("1-2 a").unwrap(), true),
+            Err("No 4-character password of 'ab' can pass the rule (range and not range) for the policy 1-2 a"
+                .to_string())
+        );
+        assert!(generator.generate(&range, &parse_policy("0-9 a").unwrap(), false).is_err());
+        assert!(generator.generate(&"index".parse().unwrap(), &parse_policy("0-2 a").unwrap(), true).is_err());
+        assert!(generator.generate(&range, &parse_policy("1-3 a; length 8-20").unwrap(), true).is_err());
+
+        // With only the policy's character to use, every place holds it
+        let mut only_a = PasswordGenerator::new(3, "a", 1).unwrap();
+        assert_eq!(only_a.generate(&range, &parse_policy("1-3 a").unwrap(), true), Ok("aaa".to_string()));
+        assert!(only_a.generate(&range, &parse_policy("1-3 a").unwrap(), false).is_err());
+        assert!(PasswordGenerator::new(3, "", 1).is_err());
+    }
+
+    #[test]
+    fn files_have_a_known_valid_count() {
+        for rule in RULES.iter() {
+            let mut generator = PasswordGenerator::new(8, "abcdefghijklmnopqrstuvwxyz", 2020).unwrap();
+            let file = generator.generate_file(&rule.parse().unwrap(), None, 50, 17).unwrap();
+
+            assert_eq!(file.lines().count(), 50);
+            assert_eq!(
+                report_rule(&file, rule, Positions::default(), false).unwrap(),
+                format!("17 of 50 passwords match the rule {}", rule.parse::<PolicyRule>().unwrap())
+            );
+        }
+
+        let mut generator = PasswordGenerator::new(8, "ab", 1).unwrap();
+        let policy = parse_policy("1-3 a").unwrap();
+        let fixed = generator.generate_file(&"index".parse().unwrap(), Some(&policy), 4, 2).unwrap();
+        assert!(fixed.lines().all(|line| line.starts_with("1-3 a: ")));
+        assert!(generator.generate_file(&"index".parse().unwrap(), None, 3, 4).is_err());
+    }
+}
\ No newline at end of file
@@ -1,78 +1,432 @@
+mod audit;
+mod generator;
 pub mod parsers;
 pub mod policies;
 
-use crate::days_of_advent::common::io;
+pub use audit::report_audit;
+pub use generator::{parse_policy, PasswordGenerator};
+pub use parsers::throughput::report_parser_throughput;
 
-use crate::days_of_advent::day02::parsers::regex_range_password_policy_parser::RegexRangePasswordPolicyParser;
-use crate::days_of_advent::day02::parsers::regex_index_password_policy_parser::RegexIndexPasswordPolicyParser;
-use crate::days_of_advent::day02::parsers::range_password_policy_parser::RangePasswordPolicyParser;
-use crate::days_of_advent::day02::parsers::index_password_policy_parser::IndexPasswordPolicyParser;
+use crate::days_of_advent::common::budget::Budget;
+use crate::days_of_advent::common::io::{self, PuzzleInput};
+use crate::days_of_advent::common::puzzle::{Answer, Puzzle};
+
+use crate::days_of_advent::day02::parsers::byte_password_policy_parser::BytePasswordPolicyParser;
+use crate::days_of_advent::day02::parsers::parse_error::ParseError;
+use crate::days_of_advent::day02::parsers::password_line::PasswordLine;
+use crate::days_of_advent::day02::parsers::password_policy_parser::{PasswordPolicyParser, PolicyRecordParser};
+use crate::days_of_advent::day02::parsers::policy_record::{FromPolicyRecord, PolicyRecord};
+use crate::days_of_advent::day02::policies::combinators::CombinesPolicies;
+use crate::days_of_advent::day02::policies::extra_policy::LinePolicy;
+use crate::days_of_advent::day02::policies::policy_rule::PolicyRule;
+use crate::days_of_advent::day02::policies::positions::Positions;
 use crate::days_of_advent::day02::policies::range_password_policy::RangePasswordPolicy;
 use crate::days_of_advent::day02::policies::index_password_policy::IndexPasswordPolicy;
 use crate::days_of_advent::day02::policies::validates_password::ValidatesPassword;
+use crate::days_of_advent::day02::policies::violation::describe_violations;
+
+/// The Day 02 puzzle, counting the passwords that match each policy
+pub struct PasswordPhilosophy;
+
+impl Puzzle for PasswordPhilosophy {
+    fn day(&self) -> usize {
+        2
+    }
+
+    fn title(&self) -> &'static str {
+        "Password Philosophy"
+    }
+
+    fn description(&self) -> &'static str {
+        "Count the passwords that match the policies"
+    }
+
+    fn version(&self) -> &'static str {
+        "6"
+    }
+
+    fn solve(&self, input: &str) -> Result<Vec<Answer>, String> {
+        let input = PuzzleInput::new(input);
+
+        let (range_policies, range_rejected) =
+            parse_password_file::<LinePolicy<RangePasswordPolicy>>(&input, &BytePasswordPolicyParser);
+        let (index_policies, index_rejected) =
+            parse_password_file::<LinePolicy<IndexPasswordPolicy>>(&input, &BytePasswordPolicyParser);
 
-pub fn run() -> () {
-    let puzzle_input = io::load_input_from_file("day02");
-    if puzzle_input.is_err() {
-        panic!("Could not load entries from file");
-    }
-    let puzzle_input = puzzle_input.unwrap();
-
-    let regex_range_parser = RegexRangePasswordPolicyParser::new();
-    let regex_index_parser = RegexIndexPasswordPolicyParser::new();
-
-    let range_policies: Vec<(String, RangePasswordPolicy)> = puzzle_input
-        .lines()
-        .map(|line| regex_range_parser.parse(line))
-        .collect();
-    let index_policies: Vec<(String, IndexPasswordPolicy)> = puzzle_input
-        .lines()
-        .map(|line| regex_index_parser.parse(line))
-        .collect();
-
-    let num_valid_range_passwords = count_valid_passwords(&range_policies).unwrap();
-    let num_valid_index_passwords = count_valid_passwords(&index_policies).unwrap();
-
-    let results = String::from(format!(
-        "The given password file has {} passwords that match the range policy\n\
-         The given password file has {} passwords that match the index policy",
-         num_valid_range_passwords,
-         num_valid_index_passwords
-    ));
-
-    let report = io::format_day_report(2, "Password Philosophy", "Count the passwords that match the policies", &results);
-    println!("{}", &report);
+        let range_tally = Tally {
+            valid: count_valid_passwords(&range_policies)?,
+            rejected: range_rejected,
+            failing: explain_invalid_passwords(&range_policies, "range"),
+        };
+        let index_tally = Tally {
+            valid: count_valid_passwords(&index_policies)?,
+            rejected: index_rejected,
+            failing: explain_invalid_passwords(&index_policies, "index"),
+        };
+
+        Ok(answers(range_tally, index_tally))
+    }
+
+    fn solve_reader(&self, reader: &mut dyn std::io::BufRead, budget: &Budget) -> Result<Vec<Answer>, String> {
+        let (range_tally, index_tally) = count_valid_passwords_in_lines(budget.meter(io::lines(reader)))?;
+
+        Ok(answers(range_tally, index_tally))
+    }
+}
+
+/// How many passwords match a policy, the lines that could not be read for
+/// it, and a description of each password that fails it
+#[derive(Debug, Default, PartialEq)]
+struct Tally {
+    valid: u32,
+    rejected: Vec<ParseError>,
+    failing: Vec<String>,
+}
+
+impl Tally {
+    /// Check the password of the record on line `line` against the policy
+    /// `P`, or set the line aside if `P` cannot be made of the record
+    fn count<P: FromPolicyRecord + ValidatesPassword + std::fmt::Display>(
+        &mut self,
+        line: usize,
+        text: &str,
+        record: &PolicyRecord<'_>,
+        policy_name: &str,
+    ) {
+        match P::from_record(record) {
+            Ok(policy) if policy.is_password_valid(record.password) => self.valid += 1,
+            Ok(policy) => self.failing.push(explain_invalid_password(line, record.password, &policy, policy_name)),
+            Err(invalid) => self.rejected.push(ParseError::at_number(text, invalid).on_line(line)),
+        }
+    }
+}
+
+/// The answers for both policies, however the input was read
+fn answers(range_tally: Tally, index_tally: Tally) -> Vec<Answer> {
+    let answer = |tally: Tally, policy_name: &str| {
+        let summary = format!(
+            "The given password file has {} passwords that match the {} policy",
+            tally.valid, policy_name
+        );
+        let summary = match tally.rejected.len() {
+            0 => summary,
+            1 => format!("{}, and 1 line that could not be read", summary),
+            rejected => format!("{}, and {} lines that could not be read", summary, rejected),
+        };
+        let mut explanation = explain_rejected_lines(&tally.rejected);
+        explanation.extend(tally.failing);
+        Answer::new(tally.valid, summary).explained_by(explanation)
+    };
+
+    vec![answer(range_tally, "range"), answer(index_tally, "index")]
+}
+
+/// Read a policy and password from each non-blank line, numbering them by
+/// line and setting aside the lines that do not parse
+fn parse_password_file<'a, P: ValidatesPassword>(
+    input: &'a PuzzleInput,
+    parser: &impl PasswordPolicyParser<P>,
+) -> (Vec<(usize, &'a str, P)>, Vec<ParseError>) {
+    let mut policies = vec![];
+    let mut rejected = vec![];
+    for (idx, line) in input.lines().enumerate().filter(|(_, line)| !line.trim().is_empty()) {
+        match parser.parse(line) {
+            Ok((password, policy)) => policies.push((idx + 1, password, policy)),
+            Err(e) => rejected.push(e.on_line(idx + 1)),
+        }
+    }
+    (policies, rejected)
+}
+
+/// Count the passwords that match the range and index policies a line at a
+/// time, so that files of any size are read without holding them in memory.
+/// Only the lines that are rejected or fail are kept, to explain the counts.
+/// Each line is parsed once and read as both policies
+fn count_valid_passwords_in_lines<S: AsRef<str>>(
+    lines: impl Iterator<Item = Result<S, String>>,
+) -> Result<(Tally, Tally), String> {
+    let (mut range_tally, mut index_tally) = (Tally::default(), Tally::default());
+    for (idx, line) in lines.enumerate() {
+        let line = line?;
+        let line = line.as_ref();
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        match BytePasswordPolicyParser.parse_record(line) {
+            Ok(record) => {
+                range_tally.count::<LinePolicy<RangePasswordPolicy>>(idx + 1, line, &record, "range");
+                index_tally.count::<LinePolicy<IndexPasswordPolicy>>(idx + 1, line, &record, "index");
+            }
+            Err(e) => {
+                range_tally.rejected.push(e.clone().on_line(idx + 1));
+                index_tally.rejected.push(e.on_line(idx + 1));
+            }
+        }
+    }
+    Ok((range_tally, index_tally))
 }
 
 fn count_valid_passwords(
-    passwords_with_policies: &[(String, impl ValidatesPassword)],
-) -> Result<u32, &str> {
+    passwords_with_policies: &[(usize, &str, impl ValidatesPassword)],
+) -> Result<u32, String> {
     let mut valid_passwords: u32 = 0;
     for entry in passwords_with_policies {
-        if entry.1.is_password_valid(&entry.0.to_string()) {
+        if entry.2.is_password_valid(entry.1) {
             valid_passwords += 1;
         }
     }
     Ok(valid_passwords)
 }
 
+/// Count the passwords in a file that match a rule combining the policies
+/// on each line, such as `range and not index`. Passwords must also pass any
+/// extra policies their line adds. Index policies read their positions as
+/// `positions` says. With `explain`, each failing password is listed with
+/// the reasons it fails
+pub fn report_rule(input: &str, rule: &str, positions: Positions, explain: bool) -> Result<String, String> {
+    let rule: PolicyRule = rule.parse()?;
+    let input = PuzzleInput::new(input);
+
+    let mut policies = vec![];
+    let mut num_rejected = 0;
+    for (idx, line) in input.lines().enumerate().filter(|(_, line)| !line.trim().is_empty()) {
+        match BytePasswordPolicyParser.parse_record(line) {
+            Ok(record) => match rule.policy(&record, positions) {
+                Ok(policy) => policies.push((idx + 1, record.password, policy.and(record.extras))),
+                Err(_) => num_rejected += 1,
+            },
+            Err(_) => num_rejected += 1,
+        }
+    }
+
+    let summary = format!(
+        "{} of {} passwords match the rule {}",
+        count_valid_passwords(&policies)?,
+        policies.len(),
+        rule
+    );
+    let mut report = match num_rejected {
+        0 => summary,
+        1 => format!("{}, and 1 line could not be read", summary),
+        n => format!("{}, and {} lines could not be read", summary, n),
+    };
+    if explain {
+        for (line, password, policy) in &policies {
+            let violations = policy.violations(password);
+            if !violations.is_empty() {
+                report += &format!("\nLine {}: '{}' fails: {}", line, password, describe_violations(&violations));
+            }
+        }
+    }
+    Ok(report)
+}
+
+/// Rewrite a password file with every line in its canonical form, dropping
+/// blank lines and line endings other than `\n`. Lines that cannot be read
+/// are kept as they are, and returned with the reason
+pub fn normalize_password_file(input: &str) -> (String, Vec<ParseError>) {
+    let input = PuzzleInput::new(input);
+
+    let mut normalized = String::new();
+    let mut rejected = vec![];
+    for (idx, line) in input.lines().enumerate().filter(|(_, line)| !line.trim().is_empty()) {
+        let parsed: Result<(&str, LinePolicy<RangePasswordPolicy>), ParseError> = BytePasswordPolicyParser.parse(line);
+        match parsed {
+            Ok((password, policy)) => normalized += &PasswordLine { policy: &policy, password }.to_string(),
+            Err(e) => {
+                normalized += line;
+                rejected.push(e.on_line(idx + 1));
+            }
+        }
+        normalized.push('\n');
+    }
+    (normalized, rejected)
+}
+
+/// Describe each line that could not be read, and why
+fn explain_rejected_lines(rejected: &[ParseError]) -> Vec<String> {
+    rejected.iter().map(|e| format!("Rejected: {}", e)).collect()
+}
+
+/// Describe each password that fails its policy and why, by line number
+fn explain_invalid_passwords<P: ValidatesPassword + std::fmt::Display>(
+    passwords_with_policies: &[(usize, &str, P)],
+    policy_name: &str,
+) -> Vec<String> {
+    passwords_with_policies
+        .iter()
+        .filter(|(_, password, policy)| !policy.is_password_valid(password))
+        .map(|(line, password, policy)| explain_invalid_password(*line, password, policy, policy_name))
+        .collect()
+}
+
+fn explain_invalid_password<P: ValidatesPassword + std::fmt::Display>(
+    line: usize,
+    password: &str,
+    policy: &P,
+    policy_name: &str,
+) -> String {
+    format!(
+        "Line {}: '{}' fails the {} policy {}: {}",
+        line,
+        password,
+        policy_name,
+        policy,
+        describe_violations(&policy.violations(password))
+    )
+}
+
 #[cfg(test)]
 mod tests {
 
     #[test]
     fn provided_acceptance_test() {
-        use super::RangePasswordPolicyParser;
         let input = "1-3 a: abcde\n1-3 b: cdefg\n2-9 c: ccccccccc";
 
         let solution = 2;
 
-        let parser = super::RegexRangePasswordPolicyParser::new();
-        let policies: Vec<(String, super::RangePasswordPolicy)> =
-            input.lines().map(|line| parser.parse(line)).collect();
+        let input = super::PuzzleInput::new(input);
+        let (policies, rejected) =
+            super::parse_password_file::<super::RangePasswordPolicy>(&input, &super::BytePasswordPolicyParser);
+        assert!(rejected.is_empty());
         let num_valid_passwords = super::count_valid_passwords(&policies).unwrap();
         assert_eq!(num_valid_passwords, solution);
     }
 
+    #[test]
+    fn count_valid_passwords_in_lines_matches_solve() {
+        use super::Puzzle;
+        let input = "1-3 a: abcde\n1-3 b: cdefg\n2-9 c: ccccccccc\n\n";
+
+        let lines = input.lines().map(Ok);
+        let (range_tally, index_tally) = super::count_valid_passwords_in_lines(lines).unwrap();
+        assert_eq!((range_tally.valid, index_tally.valid), (2, 1));
+
+        let answers = super::PasswordPhilosophy.solve_reader(&mut input.as_bytes(), &super::Budget::unlimited()).unwrap();
+        assert_eq!(answers[0].value, "2");
+        assert_eq!(answers[1].value, "1");
+    }
+
+    #[test]
+    fn explain_failing_passwords() {
+        let input = "1-3 a: abcde\n1-3 b: cdefg\n2-9 c: ccccccccc";
+
+        let input = super::PuzzleInput::new(input);
+        let (policies, _) =
+            super::parse_password_file::<super::RangePasswordPolicy>(&input, &super::BytePasswordPolicyParser);
+        let explanation = super::explain_invalid_passwords(&policies, "range");
+
+        assert_eq!(explanation, ["Line 2: 'cdefg' fails the range policy 1-3 b: found 0 'b', expected 1..=3"]);
+    }
+
+    #[test]
+    fn malformed_lines_are_rejected_not_fatal() {
+        use super::Puzzle;
+        let input = "1-3 a: abcde\n1x3 b: cdefg\n\n2-9 c: ccccccccc\n2-9 c ccccccccc\n";
+
+        let answers = super::PasswordPhilosophy.solve(input).unwrap();
+        assert_eq!(answers[0].value, "2");
+        assert_eq!(
+            answers[0].summary,
+            "The given password file has 2 passwords that match the range policy, and 2 lines that could not be read"
+        );
+        assert_eq!(
+            answers[0].explanation[..2],
+            [
+                "Rejected: Line 2, column 2: expected '-', found 'x'",
+                "Rejected: Line 5, column 6: expected ':', found ' '"
+            ]
+        );
+
+        let streamed = super::PasswordPhilosophy.solve_reader(&mut input.as_bytes(), &super::Budget::unlimited()).unwrap();
+        assert_eq!(streamed[1].summary, answers[1].summary);
+        assert_eq!(streamed[0].explanation, answers[0].explanation);
+        assert_eq!(streamed[1].explanation, answers[1].explanation);
+    }
+
+    #[test]
+    fn extra_policies_apply_to_both_readings() {
+        use super::Puzzle;
+        let input = "1-3 a; length 6-20: abcde\n1-3 a; classes digit: abcde1\n1-3 b; run 1: bbcd\n2-9 c: ccccccccc\n";
+
+        let answers = super::PasswordPhilosophy.solve(input).unwrap();
+        let streamed = super::PasswordPhilosophy.solve_reader(&mut input.as_bytes(), &super::Budget::unlimited()).unwrap();
+
+        assert_eq!((answers[0].value.as_str(), answers[1].value.as_str()), ("2", "1"));
+        assert_eq!((streamed[0].value.as_str(), streamed[1].value.as_str()), ("2", "1"));
+        assert_eq!(streamed, answers);
+        assert_eq!(
+            answers[1].explanation,
+            [
+                "Line 1: 'abcde' fails the index policy 1-3 a; length 6-20: has 5 characters, expected 6..=20",
+                "Line 3: 'bbcd' fails the index policy 1-3 b; run 1: repeats 'b' 2 times in a row, expected at most 1",
+                "Line 4: 'ccccccccc' fails the index policy 2-9 c: positions 2 and 9 both contain 'c'"
+            ]
+        );
+        assert_eq!(
+            super::report_rule(input, "range or index", Default::default(), false),
+            Ok("2 of 4 passwords match the rule (range or index)".to_string())
+        );
+        assert_eq!(
+            super::report_rule(input, "range and index", Default::default(), true),
+            Ok("1 of 4 passwords match the rule (range and index)\n\
+                Line 1: 'abcde' fails: has 5 characters, expected 6..=20\n\
+                Line 3: 'bbcd' fails: repeats 'b' 2 times in a row, expected at most 1\n\
+                Line 4: 'ccccccccc' fails: positions 2 and 9 both contain 'c'"
+                .to_string())
+        );
+    }
+
+    #[test]
+    fn position_zero_is_rejected_by_the_index_policy_only() {
+        use super::Puzzle;
+        let input = "0-2 a: ab\n1-3 a: abc\n";
+
+        let answers = super::PasswordPhilosophy.solve(input).unwrap();
+        let streamed = super::PasswordPhilosophy.solve_reader(&mut input.as_bytes(), &super::Budget::unlimited()).unwrap();
+
+        assert_eq!(answers[0].summary, "The given password file has 2 passwords that match the range policy");
+        assert_eq!(
+            answers[1].explanation,
+            ["Rejected: Line 1, column 1: expected a position of at least 1, found '0'"]
+        );
+        assert_eq!(streamed[1].summary, answers[1].summary);
+        assert_eq!(streamed, answers);
+        assert_eq!(
+            super::report_rule(input, "index", "zero".parse().unwrap(), false),
+            Ok("1 of 2 passwords match the rule index".to_string())
+        );
+    }
+
+    #[test]
+    fn normalize_keeps_unreadable_lines() {
+        let input = "01-3 a: abcde\r\n\r\n1x3 b: cdefg\r\n2-9 c; length 01-20: ccccccccc";
+
+        let (normalized, rejected) = super::normalize_password_file(input);
+
+        assert_eq!(normalized, "1-3 a: abcde\n1x3 b: cdefg\n2-9 c; length 1-20: ccccccccc\n");
+        assert_eq!(rejected, [super::ParseError::locate("1x3 b: cdefg").on_line(3)]);
+        assert_eq!(super::normalize_password_file(&normalized).0, normalized);
+    }
+
+    #[test]
+    fn count_compound_rules() {
+        use super::policies::combinators::{And, Not};
+        let input = super::PuzzleInput::new("1-3 a: abcde\n1-3 b: cdefg\n2-9 c: ccccccccc\n1-3 a: aba\n");
+
+        let (policies, _) = super::parse_password_file::<And<super::RangePasswordPolicy, Not<super::IndexPasswordPolicy>>>(
+            &input,
+            &super::BytePasswordPolicyParser,
+        );
+
+        assert_eq!(super::count_valid_passwords(&policies), Ok(2));
+        assert_eq!(
+            super::report_rule("1-3 a: abcde\n1-3 b: cdefg\n2-9 c: ccccccccc\n1-3 a: aba\nbad\n", "range and not index", Default::default(), false),
+            Ok("2 of 4 passwords match the rule (range and not index), and 1 line could not be read".to_string())
+        );
+    }
+
     #[test]
     fn check_password_1() {
         use super::ValidatesPassword;
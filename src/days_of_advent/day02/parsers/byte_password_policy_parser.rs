@@ -0,0 +1,144 @@
+use super::parse_error::ParseError;
+use super::password_policy_parser::PolicyRecordParser;
+use super::policy_clauses::parse_clauses;
+use super::policy_record::PolicyRecord;
+
+/// Reads policy lines a byte at a time. Lines with only an `a-b c` policy
+/// are read without allocating, and the password is borrowed from the line.
+/// Lines that do not parse are handed to `ParseError::locate` to find out why
+pub struct BytePasswordPolicyParser;
+
+impl PolicyRecordParser for BytePasswordPolicyParser {
+    fn parse_record<'a>(&self, string: &'a str) -> Result<PolicyRecord<'a>, ParseError> {
+        parse_bytes(string).ok_or_else(|| ParseError::locate(string))
+    }
+}
+
+fn parse_bytes(string: &str) -> Option<PolicyRecord<'_>> {
+    let bytes = string.as_bytes();
+
+    let (first, pos) = number(bytes, 0)?;
+    let pos = literal(bytes, pos, b'-')?;
+    let (second, pos) = number(bytes, pos)?;
+    let pos = literal(bytes, pos, b' ')?;
+    let character = string.get(pos..)?.chars().next()?;
+    let mut pos = pos + character.len_utf8();
+
+    let mut extras = vec![];
+    if bytes.get(pos) == Some(&b';') {
+        let end = pos + string[pos..].find(": ")?;
+        extras = parse_clauses(&string[pos..end], string[..pos].chars().count() + 1).ok()?;
+        pos = end;
+    }
+    let pos = literal(bytes, pos, b':')?;
+    let pos = literal(bytes, pos, b' ')?;
+
+    Some(PolicyRecord {
+        numbers: (first, second),
+        character,
+        extras,
+        password: &string[pos..],
+    })
+}
+
+/// Read the ASCII digits from `start`, giving the number and the position
+/// after it. Numbers too big for a `usize` are not read
+fn number(bytes: &[u8], start: usize) -> Option<(usize, usize)> {
+    let mut value: usize = 0;
+    let mut pos = start;
+    while let Some(digit) = bytes.get(pos).filter(|b| b.is_ascii_digit()) {
+        value = value.checked_mul(10)?.checked_add((digit - b'0') as usize)?;
+        pos += 1;
+    }
+    if pos == start {
+        return None;
+    }
+    Some((value, pos))
+}
+
+fn literal(bytes: &[u8], pos: usize, wanted: u8) -> Option<usize> {
+    match bytes.get(pos) {
+        Some(b) if *b == wanted => Some(pos + 1),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days_of_advent::common::io;
+    use crate::days_of_advent::day02::parsers::regex_password_policy_parser::RegexPasswordPolicyParser;
+
+    /// Well formed, though not as the puzzle input writes them
+    const UNUSUAL: [&str; 5] = [
+        "1-3 a: ",
+        "1-3 é: café",
+        "1-3 :: a:b: c",
+        "1-3  : spaced ",
+        "01-003 a: abc",
+    ];
+
+    const MALFORMED: [&str; 18] = [
+        "",
+        "1-3 a:",
+        "1-3 a abcde",
+        "-3 a: abcde",
+        "1--3 a: abcde",
+        "1-3 ab: abcde",
+        "1-99999999999999999999 a: abc",
+        "١-3 a: arabic digit",
+        " 1-3 a: leading space",
+        "1-3 a; length 8: abc",
+        "1-3 a; length 20-8: abc",
+        "1-3 a; size 8-20: abc",
+        "1-3 a; classes digit,punctuation: abc",
+        "1-3 a; regex (: abc",
+        "1-3 a; run 2",
+        "1-3 a;run 2: abc",
+        "1-3 a; banned ,admin: abc",
+        "1-3 é; run x: abc",
+    ];
+
+    const WITH_EXTRAS: [&str; 4] = [
+        "1-3 a; length 8-20; classes digit,upper; banned admin,password; regex ^[a-z]; run 2: abcde",
+        "1-3 é; run 1: café",
+        "1-3 a; regex a: b: c",
+        "2-9 ;; run 3: ;;;",
+    ];
+
+    #[test]
+    fn agrees_with_regex_parser() {
+        let regex_parser = RegexPasswordPolicyParser::new();
+        let input = io::load_input_from_file("day02").unwrap();
+
+        let fixtures = UNUSUAL.iter().chain(MALFORMED.iter()).chain(WITH_EXTRAS.iter()).copied();
+        for line in input.lines().chain(fixtures) {
+            assert_eq!(
+                BytePasswordPolicyParser.parse_record(line),
+                regex_parser.parse_record(line),
+                "{:?}",
+                line
+            );
+        }
+    }
+
+    #[test]
+    fn fixtures_are_what_they_say() {
+        for line in UNUSUAL.iter().chain(WITH_EXTRAS.iter()) {
+            assert!(BytePasswordPolicyParser.parse_record(line).is_ok(), "{:?}", line);
+        }
+        for line in MALFORMED.iter() {
+            assert!(BytePasswordPolicyParser.parse_record(line).is_err(), "{:?}", line);
+        }
+    }
+
+    #[test]
+    fn password_is_borrowed() {
+        let line = String::from("2-9 c: ccccccccc");
+
+        let record = BytePasswordPolicyParser.parse_record(&line).unwrap();
+
+        assert_eq!(record, PolicyRecord { numbers: (2, 9), character: 'c', extras: vec![], password: "ccccccccc" });
+        assert_eq!(record.password.as_ptr(), line[7..].as_ptr());
+    }
+}
\ No newline at end of file
@@ -1,5 +0,0 @@
-use crate::days_of_advent::day02::policies::index_password_policy::IndexPasswordPolicy;
-
-pub trait IndexPasswordPolicyParser {
-    fn parse(&self, string: &str) -> (String, IndexPasswordPolicy);
-}
\ No newline at end of file
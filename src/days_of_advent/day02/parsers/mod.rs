@@ -1,4 +1,8 @@
-pub mod index_password_policy_parser;
-pub mod range_password_policy_parser;
-pub mod regex_index_password_policy_parser;
-pub mod regex_range_password_policy_parser;
\ No newline at end of file
+pub mod byte_password_policy_parser;
+pub mod parse_error;
+pub mod password_line;
+pub mod password_policy_parser;
+pub mod policy_clauses;
+pub mod policy_record;
+pub mod regex_password_policy_parser;
+pub mod throughput;
\ No newline at end of file
@@ -0,0 +1,180 @@
+use super::policy_clauses::parse_clauses;
+use super::policy_record::InvalidNumber;
+
+/// A piece of the `a-b c: password` syntax, or of the extra policies that
+/// may follow `a-b c`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Token {
+    Number,
+    Dash,
+    Space,
+    Character,
+    Colon,
+    Semicolon,
+    Policy,
+    Range,
+    AscendingRange,
+    CharClass,
+    Word,
+    Regex,
+    Position,
+}
+
+impl std::fmt::Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Token::Number => write!(f, "a number"),
+            Token::Dash => write!(f, "'-'"),
+            Token::Space => write!(f, "' '"),
+            Token::Character => write!(f, "a character"),
+            Token::Colon => write!(f, "':'"),
+            Token::Semicolon => write!(f, "';'"),
+            Token::Policy => write!(f, "a policy such as 'length 8-20'"),
+            Token::Range => write!(f, "a range such as '8-20'"),
+            Token::AscendingRange => write!(f, "a range whose minimum is at most its maximum"),
+            Token::CharClass => write!(f, "one of digit, upper, lower or symbol"),
+            Token::Word => write!(f, "banned words separated by ','"),
+            Token::Regex => write!(f, "a valid regex"),
+            Token::Position => write!(f, "a position of at least 1"),
+        }
+    }
+}
+
+/// Where a line strays from the `a-b c: password` syntax. Lines and columns
+/// count from 1, and columns count characters
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub expected: Token,
+    /// The text found instead, or `None` at the end of the line
+    pub found: Option<String>,
+}
+
+impl ParseError {
+    /// Find the first place `string` strays from `a-b c: password`, as if it
+    /// were the first line. A number too big to read counts as straying, as
+    /// does an extra policy that cannot be read
+    pub fn locate(string: &str) -> ParseError {
+        const SYNTAX: [Token; 7] = [
+            Token::Number,
+            Token::Dash,
+            Token::Number,
+            Token::Space,
+            Token::Character,
+            Token::Colon,
+            Token::Space,
+        ];
+
+        let mut rest = string;
+        let mut column = 1;
+        for expected in SYNTAX.iter().copied() {
+            let error = |found: Option<String>| ParseError { line: 1, column, expected, found };
+            let next = rest.chars().next();
+            match expected {
+                Token::Number => {
+                    let digits = &rest[..rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len())];
+                    if digits.is_empty() {
+                        return error(next.map(|c| c.to_string()));
+                    }
+                    if digits.parse::<usize>().is_err() {
+                        return error(Some(digits.to_string()));
+                    }
+                    rest = &rest[digits.len()..];
+                    column += digits.len();
+                    continue;
+                }
+                Token::Character => {
+                    let character = match next {
+                        Some(character) => character,
+                        None => return error(None),
+                    };
+                    rest = &rest[character.len_utf8()..];
+                    column += 1;
+
+                    if rest.starts_with(';') {
+                        let end = rest.find(": ").unwrap_or(rest.len());
+                        if let Err(e) = parse_clauses(&rest[..end], column) {
+                            return e;
+                        }
+                        column += rest[..end].chars().count();
+                        rest = &rest[end..];
+                    }
+                    continue;
+                }
+                literal => {
+                    let wanted = match literal {
+                        Token::Dash => '-',
+                        Token::Colon => ':',
+                        _ => ' ',
+                    };
+                    match next {
+                        Some(c) if c == wanted => rest = &rest[1..],
+                        found => return error(found.map(|c| c.to_string())),
+                    }
+                }
+            }
+            column += 1;
+        }
+
+        // Every line that reaches the password fits the syntax, so parsers
+        // only call this for lines that do not
+        ParseError { line: 1, column, expected: Token::Character, found: None }
+    }
+
+    /// Point at the number a policy could not use, in a line that otherwise
+    /// fits the syntax, as if it were the first line
+    pub fn at_number(string: &str, invalid: InvalidNumber) -> ParseError {
+        let start = match invalid.number {
+            0 => 0,
+            _ => string.find('-').map_or(0, |dash| dash + 1),
+        };
+        let digits = &string[start..];
+        let digits = &digits[..digits.find(|c: char| !c.is_ascii_digit()).unwrap_or(digits.len())];
+        ParseError { line: 1, column: start + 1, expected: invalid.expected, found: Some(digits.to_string()) }
+    }
+
+    /// The same error, on line `line` of a file
+    pub fn on_line(self, line: usize) -> ParseError {
+        ParseError { line, ..self }
+    }
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "Line {}, column {}: expected {}, found ", self.line, self.column, self.expected)?;
+        match &self.found {
+            Some(found) => write!(f, "'{}'", found),
+            None => write!(f, "end of line"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn locate_first_stray_token() {
+        let locate = |s: &str| ParseError::locate(s).to_string();
+
+        assert_eq!(locate(""), "Line 1, column 1: expected a number, found end of line");
+        assert_eq!(locate("1x3 a: abc"), "Line 1, column 2: expected '-', found 'x'");
+        assert_eq!(locate("10-3a: abc"), "Line 1, column 5: expected ' ', found 'a'");
+        assert_eq!(locate("1-3 a abc"), "Line 1, column 6: expected ':', found ' '");
+        assert_eq!(locate("1-3 a:abc"), "Line 1, column 7: expected ' ', found 'a'");
+        assert_eq!(
+            locate("1-99999999999999999999 a: abc"),
+            "Line 1, column 3: expected a number, found '99999999999999999999'"
+        );
+        assert_eq!(ParseError::locate("1-").on_line(4).line, 4);
+        assert_eq!(
+            ParseError::at_number("10-0 a: abc", InvalidNumber { number: 1, expected: Token::Position }).to_string(),
+            "Line 1, column 4: expected a position of at least 1, found '0'"
+        );
+        assert_eq!(locate("1-3 a; run 2 abc"), "Line 1, column 12: expected a number, found '2 abc'");
+        assert_eq!(locate("1-3 a; run 2:abc"), "Line 1, column 12: expected a number, found '2:abc'");
+        assert_eq!(locate("1-3 a; length 1-2; run x: abc"), "Line 1, column 24: expected a number, found 'x'");
+        assert_eq!(locate("1-3 a; run 2"), "Line 1, column 13: expected ':', found end of line");
+    }
+}
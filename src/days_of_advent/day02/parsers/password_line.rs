@@ -0,0 +1,77 @@
+/// A password and its policy, written back as the `a-b c: password` line a
+/// parser reads. Numbers lose any leading zeros and extra policies are
+/// written as `; length 8-20`, so reading a line and writing it back gives
+/// the line in its canonical form
+pub struct PasswordLine<'a, P> {
+    pub policy: &'a P,
+    pub password: &'a str,
+}
+
+impl<P: std::fmt::Display> std::fmt::Display for PasswordLine<'_, P> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}: {}", self.policy, self.password)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days_of_advent::common::io;
+    use crate::days_of_advent::day02::parsers::byte_password_policy_parser::BytePasswordPolicyParser;
+    use crate::days_of_advent::day02::parsers::password_policy_parser::PasswordPolicyParser;
+    use crate::days_of_advent::day02::policies::extra_policy::LinePolicy;
+    use crate::days_of_advent::day02::policies::index_password_policy::IndexPasswordPolicy;
+    use crate::days_of_advent::day02::policies::range_password_policy::RangePasswordPolicy;
+    use crate::days_of_advent::day02::policies::validates_password::ValidatesPassword;
+
+    /// Read a line as `P` and write it back
+    fn rewrite<P>(line: &str) -> String
+    where
+        P: ValidatesPassword + std::fmt::Display,
+        BytePasswordPolicyParser: PasswordPolicyParser<P>,
+    {
+        let (password, policy): (&str, P) = BytePasswordPolicyParser.parse(line).unwrap();
+        PasswordLine { policy: &policy, password }.to_string()
+    }
+
+    #[test]
+    fn stored_input_is_canonical() {
+        let input = io::load_input_from_file("day02").unwrap();
+
+        for line in input.lines() {
+            assert_eq!(rewrite::<RangePasswordPolicy>(line), line);
+            assert_eq!(rewrite::<IndexPasswordPolicy>(line), line);
+        }
+    }
+
+    #[test]
+    fn lines_are_written_canonically() {
+        assert_eq!(rewrite::<RangePasswordPolicy>("01-003 a: abc"), "1-3 a: abc");
+        assert_eq!(
+            rewrite::<LinePolicy<RangePasswordPolicy>>("1-3 a; length 08-20; classes digit,upper: x"),
+            "1-3 a; length 8-20; classes digit,upper: x"
+        );
+    }
+
+    #[test]
+    fn rewritten_lines_read_back_the_same() {
+        let lines = [
+            "1-3 a: abcde",
+            "2-9 c: ",
+            "1-3 :: a:b: c",
+            "1-3  : spaced ",
+            "1-3 é: café",
+            "2-9 ;; run 3: ;;;",
+            "007-3 x; banned admin,root; regex ^[a-z]+$; run 02: x: y",
+        ];
+
+        for line in lines.iter() {
+            let rewritten = rewrite::<LinePolicy<RangePasswordPolicy>>(line);
+            let original: (&str, LinePolicy<RangePasswordPolicy>) = BytePasswordPolicyParser.parse(line).unwrap();
+            let reread: (&str, LinePolicy<RangePasswordPolicy>) = BytePasswordPolicyParser.parse(&rewritten).unwrap();
+
+            assert_eq!(reread, original, "{:?}", line);
+            assert_eq!(rewrite::<LinePolicy<RangePasswordPolicy>>(&rewritten), rewritten);
+        }
+    }
+}
\ No newline at end of file
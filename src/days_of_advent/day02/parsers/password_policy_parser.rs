@@ -0,0 +1,23 @@
+use super::parse_error::ParseError;
+use super::policy_record::{FromPolicyRecord, PolicyRecord};
+use crate::days_of_advent::day02::policies::validates_password::ValidatesPassword;
+
+/// Reads a password and its policy of type `P` from a line, borrowing the
+/// password from the line
+pub trait PasswordPolicyParser<P: ValidatesPassword> {
+    fn parse<'a>(&self, string: &'a str) -> Result<(&'a str, P), ParseError>;
+}
+
+/// Reads the `a-b c: password` syntax every policy shares. Such a parser
+/// reads any policy that can be built from a record
+pub trait PolicyRecordParser {
+    fn parse_record<'a>(&self, string: &'a str) -> Result<PolicyRecord<'a>, ParseError>;
+}
+
+impl<T: PolicyRecordParser, P: FromPolicyRecord + ValidatesPassword> PasswordPolicyParser<P> for T {
+    fn parse<'a>(&self, string: &'a str) -> Result<(&'a str, P), ParseError> {
+        let record = self.parse_record(string)?;
+        let policy = P::from_record(&record).map_err(|invalid| ParseError::at_number(string, invalid))?;
+        Ok((record.password, policy))
+    }
+}
\ No newline at end of file
@@ -0,0 +1,124 @@
+use regex::Regex;
+
+use super::parse_error::{ParseError, Token};
+use crate::days_of_advent::day02::policies::banned_substring_password_policy::BannedSubstringPasswordPolicy;
+use crate::days_of_advent::day02::policies::char_class_password_policy::{CharClass, CharClassPasswordPolicy};
+use crate::days_of_advent::day02::policies::extra_policy::ExtraPolicy;
+use crate::days_of_advent::day02::policies::length_password_policy::LengthPasswordPolicy;
+use crate::days_of_advent::day02::policies::max_run_password_policy::MaxRunPasswordPolicy;
+use crate::days_of_advent::day02::policies::regex_password_policy::RegexPasswordPolicy;
+
+/// Read the extra policies that follow a line's `a-b c` policy, such as
+/// `; length 8-20; classes digit,upper; banned admin,password; regex ^[a-z];
+/// run 2`. `column` is where `clauses` starts in its line. Clauses end at
+/// the next `; `, so a regex cannot contain one
+pub fn parse_clauses(clauses: &str, column: usize) -> Result<Vec<ExtraPolicy>, ParseError> {
+    let mut extras = vec![];
+    let mut rest = clauses;
+    let mut column = column;
+    while !rest.is_empty() {
+        let error = |offset: usize, expected: Token, found: Option<&str>| ParseError {
+            line: 1,
+            column: column + offset,
+            expected,
+            found: found.map(|found| found.to_string()),
+        };
+        let first_char = |s: &str| s.chars().next().map(|c| c.to_string());
+
+        rest = rest.strip_prefix(';').ok_or_else(|| error(0, Token::Semicolon, first_char(rest).as_deref()))?;
+        rest = rest.strip_prefix(' ').ok_or_else(|| error(1, Token::Space, first_char(rest).as_deref()))?;
+
+        let clause = match rest.find("; ") {
+            Some(end) => &rest[..end],
+            None => rest,
+        };
+        let (keyword, argument) = match clause.split_once(' ') {
+            Some(split) => split,
+            None => {
+                let found = first_char(&rest[clause.len()..]);
+                return Err(error(2 + clause.chars().count(), Token::Space, found.as_deref()));
+            }
+        };
+        let argument_column = 2 + keyword.chars().count() + 1;
+        let bad_argument = |expected: Token, found: &str| error(argument_column, expected, Some(found));
+
+        extras.push(match keyword {
+            "length" => {
+                let range = argument
+                    .split_once('-')
+                    .and_then(|(min, max)| Some((min.parse().ok()?, max.parse().ok()?)))
+                    .ok_or_else(|| bad_argument(Token::Range, argument))?;
+                if range.0 > range.1 {
+                    return Err(bad_argument(Token::AscendingRange, argument));
+                }
+                ExtraPolicy::Length(LengthPasswordPolicy { range: std::ops::RangeInclusive::new(range.0, range.1) })
+            }
+            "classes" => {
+                let mut classes = vec![];
+                let mut offset = argument_column;
+                for class in argument.split(',') {
+                    classes.push(class.parse::<CharClass>().map_err(|_| error(offset, Token::CharClass, Some(class)))?);
+                    offset += class.chars().count() + 1;
+                }
+                ExtraPolicy::Classes(CharClassPasswordPolicy { classes })
+            }
+            "banned" => {
+                if argument.split(',').any(|word| word.is_empty()) {
+                    return Err(bad_argument(Token::Word, argument));
+                }
+                let banned = argument.split(',').map(|word| word.to_string()).collect();
+                ExtraPolicy::Banned(BannedSubstringPasswordPolicy { banned })
+            }
+            "regex" => {
+                let regex = Regex::new(argument).map_err(|_| bad_argument(Token::Regex, argument))?;
+                ExtraPolicy::Regex(RegexPasswordPolicy { regex })
+            }
+            "run" => {
+                let max_run = argument.parse().map_err(|_| bad_argument(Token::Number, argument))?;
+                ExtraPolicy::MaxRun(MaxRunPasswordPolicy { max_run })
+            }
+            _ => return Err(error(2, Token::Policy, Some(keyword))),
+        });
+
+        column += 2 + clause.chars().count();
+        rest = &rest[clause.len()..];
+    }
+    Ok(extras)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_every_kind_of_clause() {
+        let extras = parse_clauses("; length 8-20; classes digit,upper; banned admin,password; regex ^[a-z]; run 2", 6)
+            .unwrap();
+        let shown: Vec<String> = extras.iter().map(|extra| extra.to_string()).collect();
+
+        assert_eq!(
+            shown,
+            ["length 8-20", "classes digit,upper", "banned admin,password", "regex ^[a-z]", "run 2"]
+        );
+    }
+
+    #[test]
+    fn clause_errors_point_at_the_clause() {
+        let error = |clauses: &str| parse_clauses(clauses, 6).unwrap_err().to_string();
+
+        assert_eq!(error("; size 8"), "Line 1, column 8: expected a policy such as 'length 8-20', found 'size'");
+        assert_eq!(error("; length 8"), "Line 1, column 15: expected a range such as '8-20', found '8'");
+        assert_eq!(
+            error("; length 20-8"),
+            "Line 1, column 15: expected a range whose minimum is at most its maximum, found '20-8'"
+        );
+        assert_eq!(
+            error("; run 2; classes digit,punctuation"),
+            "Line 1, column 29: expected one of digit, upper, lower or symbol, found 'punctuation'"
+        );
+        assert_eq!(error("; regex ("), "Line 1, column 14: expected a valid regex, found '('");
+        assert_eq!(error("; run"), "Line 1, column 11: expected ' ', found end of line");
+        assert_eq!(error("; run; run 2"), "Line 1, column 11: expected ' ', found ';'");
+        assert_eq!(error(";run 2"), "Line 1, column 7: expected ' ', found 'r'");
+    }
+}
@@ -0,0 +1,27 @@
+use super::parse_error::Token;
+use crate::days_of_advent::day02::policies::extra_policy::ExtraPolicy;
+
+/// A line of the `a-b c: password` syntax every policy shares, read before
+/// deciding which policy it describes
+#[derive(Debug, PartialEq)]
+pub struct PolicyRecord<'a> {
+    pub numbers: (usize, usize),
+    pub character: char,
+    /// The policies listed after `a-b c`, which every password on the line
+    /// must also pass
+    pub extras: Vec<ExtraPolicy>,
+    pub password: &'a str,
+}
+
+/// A number on a line that a policy cannot use, `0` for the first number
+/// and `1` for the second
+#[derive(Debug, PartialEq)]
+pub struct InvalidNumber {
+    pub number: usize,
+    pub expected: Token,
+}
+
+/// A policy that can be built from the numbers and character of a line
+pub trait FromPolicyRecord: Sized {
+    fn from_record(record: &PolicyRecord<'_>) -> Result<Self, InvalidNumber>;
+}
\ No newline at end of file
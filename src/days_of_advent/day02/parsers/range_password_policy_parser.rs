@@ -1,5 +0,0 @@
-use super::super::policies::range_password_policy::RangePasswordPolicy;
-
-pub trait RangePasswordPolicyParser {
-    fn parse(&self, string: &str) -> (String, RangePasswordPolicy);
-}
\ No newline at end of file
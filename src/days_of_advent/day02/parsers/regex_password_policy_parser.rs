@@ -0,0 +1,68 @@
+use regex::Regex;
+
+use super::parse_error::ParseError;
+use super::password_policy_parser::PolicyRecordParser;
+use super::policy_clauses::parse_clauses;
+use super::policy_record::PolicyRecord;
+
+/// Reads policy lines with a regex. The byte parser is checked against this
+/// one, as the simpler statement of the syntax
+pub struct RegexPasswordPolicyParser {
+    regex: Regex
+}
+
+impl RegexPasswordPolicyParser {
+    pub fn new() -> Self {
+        RegexPasswordPolicyParser {
+            regex: Regex::new(r"^(?P<first>\d+)\-(?P<second>\d+) (?P<character>.)(?P<clauses>;.*?)?: (?P<password>.*)$").unwrap()
+        }
+    }
+}
+
+impl PolicyRecordParser for RegexPasswordPolicyParser {
+    fn parse_record<'a>(&self, string: &'a str) -> Result<PolicyRecord<'a>, ParseError> {
+        let captures = self.regex.captures(string).ok_or_else(|| ParseError::locate(string))?;
+        let first = captures["first"].parse().map_err(|_| ParseError::locate(string))?;
+        let second = captures["second"].parse().map_err(|_| ParseError::locate(string))?;
+
+        let extras = match captures.name("clauses") {
+            Some(clauses) => parse_clauses(clauses.as_str(), string[..clauses.start()].chars().count() + 1)?,
+            None => vec![],
+        };
+
+        Ok(PolicyRecord {
+            numbers: (first, second),
+            character: captures["character"].parse().map_err(|_| ParseError::locate(string))?,
+            extras,
+            password: captures.name("password").unwrap().as_str(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days_of_advent::day02::parsers::password_policy_parser::PasswordPolicyParser;
+    use crate::days_of_advent::day02::policies::index_password_policy::IndexPasswordPolicy;
+    use crate::days_of_advent::day02::policies::range_password_policy::RangePasswordPolicy;
+
+    #[test]
+    fn test_0() {
+        let parser = RegexPasswordPolicyParser::new();
+        let record = parser.parse_record("1-3 a: abcde").unwrap();
+
+        assert_eq!(record, PolicyRecord { numbers: (1, 3), character: 'a', extras: vec![], password: "abcde" });
+        assert_eq!(parser.parse_record("1-3 a abcde").unwrap_err().column, 6);
+    }
+
+    #[test]
+    fn one_parser_for_every_policy() {
+        let parser = RegexPasswordPolicyParser::new();
+        let (password, range_policy): (&str, RangePasswordPolicy) = parser.parse("1-3 a: abcde").unwrap();
+        let (_, index_policy): (&str, IndexPasswordPolicy) = parser.parse("1-3 a: abcde").unwrap();
+
+        assert_eq!(password, "abcde");
+        assert_eq!(range_policy.range, std::ops::RangeInclusive::new(1,3));
+        assert_eq!(index_policy.indices, (1, 3));
+    }
+}
\ No newline at end of file
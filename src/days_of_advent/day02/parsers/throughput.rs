@@ -0,0 +1,68 @@
+use super::byte_password_policy_parser::BytePasswordPolicyParser;
+use super::password_policy_parser::PolicyRecordParser;
+use super::regex_password_policy_parser::RegexPasswordPolicyParser;
+
+/// Time the regex and byte parsers over the same `num_lines` generated policy
+/// lines. The same seed always generates the same lines, so runs can be
+/// compared
+pub fn report_parser_throughput(num_lines: usize, seed: u64) -> Result<String, String> {
+    let lines = generate_lines(num_lines, seed);
+    let num_bytes: usize = lines.iter().map(|line| line.len() + 1).sum();
+
+    let time = |name: &str, parser: &dyn PolicyRecordParser| {
+        let started = std::time::Instant::now();
+        let parsed = lines.iter().filter(|line| parser.parse_record(line).is_ok()).count();
+        let elapsed = started.elapsed();
+        if parsed != lines.len() {
+            return Err(format!("The {} parser read only {} of {} lines", name, parsed, lines.len()));
+        }
+        let report = format!(
+            "{}: {} lines in {:?}, {:.1} MB/s",
+            name,
+            parsed,
+            elapsed,
+            num_bytes as f64 / elapsed.as_secs_f64() / 1e6
+        );
+        Ok((report, elapsed))
+    };
+
+    let (regex_report, regex) = time("regex", &RegexPasswordPolicyParser::new())?;
+    let (bytes_report, bytes) = time("bytes", &BytePasswordPolicyParser)?;
+    Ok(format!(
+        "{}\n{}\nThe byte parser is {:.1}x faster",
+        regex_report,
+        bytes_report,
+        regex.as_secs_f64() / bytes.as_secs_f64()
+    ))
+}
+
+/// Range policy lines with small numbers and short lowercase passwords
+fn generate_lines(num_lines: usize, mut seed: u64) -> Vec<String> {
+    let mut random = |n: u64| {
+        seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        (seed >> 33) % n
+    };
+    (0..num_lines)
+        .map(|_| {
+            let min = 1 + random(10);
+            let password: String = (0..1 + random(20)).map(|_| (b'a' + random(26) as u8) as char).collect();
+            format!("{}-{} {}: {}", min, min + random(10), (b'a' + random(26) as u8) as char, password)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn report_both_parsers() {
+        let report = report_parser_throughput(100, 2020).unwrap();
+        let lines: Vec<&str> = report.lines().collect();
+
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("regex: 100 lines in "));
+        assert!(lines[1].starts_with("bytes: 100 lines in "));
+        assert_eq!(generate_lines(5, 7), generate_lines(5, 7));
+    }
+}
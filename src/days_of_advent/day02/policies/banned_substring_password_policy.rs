@@ -0,0 +1,47 @@
+use super::validates_password::ValidatesPassword;
+use super::violation::Violation;
+
+/// Valid when the password contains none of the banned words, ignoring case
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BannedSubstringPasswordPolicy {
+    pub banned: Vec<String>
+}
+
+impl std::fmt::Display for BannedSubstringPasswordPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "banned {}", self.banned.join(","))
+    }
+}
+
+impl ValidatesPassword for BannedSubstringPasswordPolicy {
+    fn is_password_valid(&self, password: &str) -> bool {
+        let password = password.to_lowercase();
+        !self.banned.iter().any(|word| password.contains(&word.to_lowercase()))
+    }
+
+    fn violations(&self, password: &str) -> Vec<Violation> {
+        let password = password.to_lowercase();
+        self.banned
+            .iter()
+            .filter(|word| password.contains(&word.to_lowercase()))
+            .map(|word| Violation::Banned(word.clone()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn banned_words_ignore_case() {
+        let policy = BannedSubstringPasswordPolicy { banned: vec!["password".to_string(), "admin".to_string()] };
+
+        assert!(!policy.is_password_valid("MyPassword1"));
+        assert!(!policy.is_password_valid("xadminx"));
+        assert!(policy.is_password_valid("correct horse"));
+        assert_eq!(policy.violations("AdminPassword")[1].to_string(), "contains the banned word 'admin'");
+        assert_eq!(policy.to_string(), "banned password,admin");
+    }
+}
@@ -0,0 +1,113 @@
+use super::validates_password::ValidatesPassword;
+use super::violation::Violation;
+
+/// A kind of character a password can be required to contain. Every class
+/// follows Unicode, so 'É' is upper and '٣' a digit
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CharClass {
+    Digit,
+    Upper,
+    Lower,
+    /// Any character that is not a letter, digit, space or control character
+    Symbol,
+}
+
+impl CharClass {
+    pub fn contains(self, c: char) -> bool {
+        match self {
+            CharClass::Digit => c.is_numeric(),
+            CharClass::Upper => c.is_uppercase(),
+            CharClass::Lower => c.is_lowercase(),
+            CharClass::Symbol => !c.is_alphanumeric() && !c.is_whitespace() && !c.is_control(),
+        }
+    }
+}
+
+impl std::str::FromStr for CharClass {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        match s {
+            "digit" => Ok(CharClass::Digit),
+            "upper" => Ok(CharClass::Upper),
+            "lower" => Ok(CharClass::Lower),
+            "symbol" => Ok(CharClass::Symbol),
+            _ => Err(format!("'{}' is not one of digit, upper, lower or symbol", s)),
+        }
+    }
+}
+
+impl std::fmt::Display for CharClass {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            CharClass::Digit => write!(f, "digit"),
+            CharClass::Upper => write!(f, "upper"),
+            CharClass::Lower => write!(f, "lower"),
+            CharClass::Symbol => write!(f, "symbol"),
+        }
+    }
+}
+
+/// Valid when the password has at least one character of every class
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CharClassPasswordPolicy {
+    pub classes: Vec<CharClass>
+}
+
+impl std::fmt::Display for CharClassPasswordPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let classes: Vec<String> = self.classes.iter().map(|c| c.to_string()).collect();
+        write!(f, "classes {}", classes.join(","))
+    }
+}
+
+impl ValidatesPassword for CharClassPasswordPolicy {
+    fn is_password_valid(&self, password: &str) -> bool {
+        self.classes.iter().all(|class| password.chars().any(|c| class.contains(c)))
+    }
+
+    fn violations(&self, password: &str) -> Vec<Violation> {
+        self.classes
+            .iter()
+            .filter(|class| !password.chars().any(|c| class.contains(c)))
+            .map(|class| Violation::MissingClass(*class))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_class_is_required() {
+        let policy = CharClassPasswordPolicy { classes: vec![CharClass::Digit, CharClass::Upper, CharClass::Symbol] };
+
+        assert!(policy.is_password_valid("Hunter2!"));
+        assert!(!policy.is_password_valid("hunter2!"));
+        assert!(!policy.is_password_valid("Hunter2"));
+        assert_eq!(
+            policy.violations("hunter"),
+            [
+                Violation::MissingClass(CharClass::Digit),
+                Violation::MissingClass(CharClass::Upper),
+                Violation::MissingClass(CharClass::Symbol)
+            ]
+        );
+        assert_eq!(policy.to_string(), "classes digit,upper,symbol");
+        assert!("punctuation".parse::<CharClass>().is_err());
+    }
+
+    #[test]
+    fn classes_follow_unicode() {
+        let policy = CharClassPasswordPolicy { classes: vec![CharClass::Digit, CharClass::Upper, CharClass::Symbol] };
+
+        assert!(policy.is_password_valid("Éclair٣€"));
+        assert!(CharClass::Lower.contains('ß'));
+        assert!(!CharClass::Symbol.contains('漢'));
+        assert!(!CharClass::Symbol.contains(' '));
+        assert!(!CharClass::Symbol.contains('\t'));
+    }
+}
@@ -0,0 +1,218 @@
+use super::validates_password::ValidatesPassword;
+use super::violation::Violation;
+use crate::days_of_advent::day02::parsers::policy_record::{FromPolicyRecord, InvalidNumber, PolicyRecord};
+
+/// Valid when both policies are
+#[derive(Debug, PartialEq)]
+pub struct And<A, B>(pub A, pub B);
+
+/// Valid when either policy is
+#[derive(Debug, PartialEq)]
+pub struct Or<A, B>(pub A, pub B);
+
+/// Valid when exactly one of the policies is
+#[derive(Debug, PartialEq)]
+pub struct Xor<A, B>(pub A, pub B);
+
+/// Valid when the policy is not
+#[derive(Debug, PartialEq)]
+pub struct Not<A>(pub A);
+
+impl<A: ValidatesPassword, B: ValidatesPassword> ValidatesPassword for And<A, B> {
+    fn is_password_valid(&self, password: &str) -> bool {
+        self.0.is_password_valid(password) && self.1.is_password_valid(password)
+    }
+
+    fn violations(&self, password: &str) -> Vec<Violation> {
+        let mut violations = self.0.violations(password);
+        violations.extend(self.1.violations(password));
+        violations
+    }
+}
+
+impl<A: ValidatesPassword, B: ValidatesPassword> ValidatesPassword for Or<A, B> {
+    fn is_password_valid(&self, password: &str) -> bool {
+        self.0.is_password_valid(password) || self.1.is_password_valid(password)
+    }
+
+    /// Both policies' violations, or none when either policy passes
+    fn violations(&self, password: &str) -> Vec<Violation> {
+        let first = self.0.violations(password);
+        if first.is_empty() {
+            return first;
+        }
+        let second = self.1.violations(password);
+        if second.is_empty() {
+            return second;
+        }
+        [first, second].concat()
+    }
+}
+
+impl<A: ValidatesPassword, B: ValidatesPassword> ValidatesPassword for Xor<A, B> {
+    fn is_password_valid(&self, password: &str) -> bool {
+        self.0.is_password_valid(password) != self.1.is_password_valid(password)
+    }
+
+    fn violations(&self, password: &str) -> Vec<Violation> {
+        let (first, second) = (self.0.violations(password), self.1.violations(password));
+        match (first.is_empty(), second.is_empty()) {
+            (true, true) => vec![Violation::BothPassed],
+            (false, false) => [first, second].concat(),
+            _ => vec![],
+        }
+    }
+}
+
+impl<A: ValidatesPassword> ValidatesPassword for Not<A> {
+    fn is_password_valid(&self, password: &str) -> bool {
+        !self.0.is_password_valid(password)
+    }
+
+    fn violations(&self, password: &str) -> Vec<Violation> {
+        self.0.negated_violations(password)
+    }
+
+    fn negated_violations(&self, password: &str) -> Vec<Violation> {
+        self.0.violations(password)
+    }
+}
+
+/// Builder helpers, so that policies read as the rule they make up, such as
+/// `range.and(index.not())`
+pub trait CombinesPolicies: ValidatesPassword + Sized {
+    fn and<B: ValidatesPassword>(self, other: B) -> And<Self, B> {
+        And(self, other)
+    }
+
+    fn or<B: ValidatesPassword>(self, other: B) -> Or<Self, B> {
+        Or(self, other)
+    }
+
+    fn xor<B: ValidatesPassword>(self, other: B) -> Xor<Self, B> {
+        Xor(self, other)
+    }
+
+    fn not(self) -> Not<Self> {
+        Not(self)
+    }
+}
+
+impl<P: ValidatesPassword> CombinesPolicies for P {}
+
+impl<A: FromPolicyRecord, B: FromPolicyRecord> FromPolicyRecord for And<A, B> {
+    fn from_record(record: &PolicyRecord<'_>) -> Result<Self, InvalidNumber> {
+        Ok(And(A::from_record(record)?, B::from_record(record)?))
+    }
+}
+
+impl<A: FromPolicyRecord, B: FromPolicyRecord> FromPolicyRecord for Or<A, B> {
+    fn from_record(record: &PolicyRecord<'_>) -> Result<Self, InvalidNumber> {
+        Ok(Or(A::from_record(record)?, B::from_record(record)?))
+    }
+}
+
+impl<A: FromPolicyRecord, B: FromPolicyRecord> FromPolicyRecord for Xor<A, B> {
+    fn from_record(record: &PolicyRecord<'_>) -> Result<Self, InvalidNumber> {
+        Ok(Xor(A::from_record(record)?, B::from_record(record)?))
+    }
+}
+
+impl<A: FromPolicyRecord> FromPolicyRecord for Not<A> {
+    fn from_record(record: &PolicyRecord<'_>) -> Result<Self, InvalidNumber> {
+        Ok(Not(A::from_record(record)?))
+    }
+}
+
+impl<A: std::fmt::Display, B: std::fmt::Display> std::fmt::Display for And<A, B> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "({} and {})", self.0, self.1)
+    }
+}
+
+impl<A: std::fmt::Display, B: std::fmt::Display> std::fmt::Display for Or<A, B> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "({} or {})", self.0, self.1)
+    }
+}
+
+impl<A: std::fmt::Display, B: std::fmt::Display> std::fmt::Display for Xor<A, B> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "({} xor {})", self.0, self.1)
+    }
+}
+
+impl<A: std::fmt::Display> std::fmt::Display for Not<A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "not {}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days_of_advent::day02::policies::index_password_policy::IndexPasswordPolicy;
+    use crate::days_of_advent::day02::policies::range_password_policy::RangePasswordPolicy;
+
+    fn range() -> RangePasswordPolicy {
+        RangePasswordPolicy { range: std::ops::RangeInclusive::new(1, 3), character: 'a' }
+    }
+
+    fn index() -> IndexPasswordPolicy {
+        IndexPasswordPolicy { indices: (1, 3), character: 'a', positions: Default::default() }
+    }
+
+    #[test]
+    fn combinators_follow_logic() {
+        // "abc" passes both policies, "aba" only the range policy, "bbb" neither
+        for (password, range_valid, index_valid) in [("abc", true, true), ("aba", true, false), ("bbb", false, false)] {
+            assert_eq!(range().and(index()).is_password_valid(password), range_valid && index_valid);
+            assert_eq!(range().or(index()).is_password_valid(password), range_valid || index_valid);
+            assert_eq!(range().xor(index()).is_password_valid(password), range_valid != index_valid);
+            assert_eq!(index().not().is_password_valid(password), !index_valid);
+        }
+    }
+
+    #[test]
+    fn combinators_aggregate_violations() {
+        let explain = |policy: &dyn ValidatesPassword, password: &str| {
+            policy.violations(password).iter().map(|v| v.to_string()).collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            explain(&range().and(index()), "aaaa"),
+            ["found 4 'a', expected 1..=3", "positions 1 and 3 both contain 'a'"]
+        );
+        assert_eq!(explain(&range().or(index()), "aba"), Vec::<String>::new());
+        assert_eq!(
+            explain(&range().or(index()), "bbb"),
+            ["found 0 'a', expected 1..=3", "neither position 1 nor 3 contains 'a'"]
+        );
+        assert_eq!(explain(&range().xor(index()), "abc"), ["passes both policies, where exactly one must pass"]);
+        assert_eq!(explain(&index().not(), "abc"), ["passes a policy it must fail"]);
+
+        assert_eq!(explain(&index().not().not(), "aba"), ["positions 1 and 3 both contain 'a'"]);
+        assert_eq!(explain(&index().not().not().not(), "abc"), ["passes a policy it must fail"]);
+        let boxed: Box<dyn ValidatesPassword> = Box::new(index().not());
+        assert_eq!(explain(&boxed.not(), "bbb"), ["neither position 1 nor 3 contains 'a'"]);
+
+        let policies: [&dyn ValidatesPassword; 6] = [
+            &range().and(index()),
+            &range().or(index()),
+            &range().xor(index()),
+            &index().not(),
+            &index().not().not(),
+            &index().not().not().not(),
+        ];
+        for password in ["", "a", "abc", "aba", "aaaa", "bbb", "bab"] {
+            for policy in policies {
+                assert_eq!(policy.violations(password).is_empty(), policy.is_password_valid(password));
+            }
+        }
+    }
+
+    #[test]
+    fn display_nested_rule() {
+        assert_eq!(range().and(index().not()).to_string(), "(1-3 a and not 1-3 a)");
+    }
+}
@@ -0,0 +1,126 @@
+use super::banned_substring_password_policy::BannedSubstringPasswordPolicy;
+use super::char_class_password_policy::CharClassPasswordPolicy;
+use super::length_password_policy::LengthPasswordPolicy;
+use super::max_run_password_policy::MaxRunPasswordPolicy;
+use super::regex_password_policy::RegexPasswordPolicy;
+use super::validates_password::ValidatesPassword;
+use super::violation::Violation;
+use crate::days_of_advent::day02::parsers::policy_record::{FromPolicyRecord, InvalidNumber, PolicyRecord};
+
+/// A policy added to a line after its `a-b c` policy, as in
+/// `1-3 a; length 8-20; run 2: password`
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExtraPolicy {
+    Length(LengthPasswordPolicy),
+    Classes(CharClassPasswordPolicy),
+    Banned(BannedSubstringPasswordPolicy),
+    Regex(RegexPasswordPolicy),
+    MaxRun(MaxRunPasswordPolicy),
+}
+
+impl ValidatesPassword for ExtraPolicy {
+    fn is_password_valid(&self, password: &str) -> bool {
+        match self {
+            ExtraPolicy::Length(policy) => policy.is_password_valid(password),
+            ExtraPolicy::Classes(policy) => policy.is_password_valid(password),
+            ExtraPolicy::Banned(policy) => policy.is_password_valid(password),
+            ExtraPolicy::Regex(policy) => policy.is_password_valid(password),
+            ExtraPolicy::MaxRun(policy) => policy.is_password_valid(password),
+        }
+    }
+
+    fn violations(&self, password: &str) -> Vec<Violation> {
+        match self {
+            ExtraPolicy::Length(policy) => policy.violations(password),
+            ExtraPolicy::Classes(policy) => policy.violations(password),
+            ExtraPolicy::Banned(policy) => policy.violations(password),
+            ExtraPolicy::Regex(policy) => policy.violations(password),
+            ExtraPolicy::MaxRun(policy) => policy.violations(password),
+        }
+    }
+}
+
+impl std::fmt::Display for ExtraPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            ExtraPolicy::Length(policy) => policy.fmt(f),
+            ExtraPolicy::Classes(policy) => policy.fmt(f),
+            ExtraPolicy::Banned(policy) => policy.fmt(f),
+            ExtraPolicy::Regex(policy) => policy.fmt(f),
+            ExtraPolicy::MaxRun(policy) => policy.fmt(f),
+        }
+    }
+}
+
+/// The policy a line describes: its `a-b c` policy read as `P`, along with
+/// any extra policies it adds. Valid only when all of them are
+#[derive(Debug, PartialEq)]
+pub struct LinePolicy<P> {
+    pub policy: P,
+    pub extras: Vec<ExtraPolicy>,
+}
+
+impl<P: FromPolicyRecord> FromPolicyRecord for LinePolicy<P> {
+    fn from_record(record: &PolicyRecord<'_>) -> Result<Self, InvalidNumber> {
+        Ok(LinePolicy {
+            policy: P::from_record(record)?,
+            extras: record.extras.clone(),
+        })
+    }
+}
+
+impl<P: ValidatesPassword> ValidatesPassword for LinePolicy<P> {
+    fn is_password_valid(&self, password: &str) -> bool {
+        self.policy.is_password_valid(password) && self.extras.is_password_valid(password)
+    }
+
+    fn violations(&self, password: &str) -> Vec<Violation> {
+        let mut violations = self.policy.violations(password);
+        violations.extend(self.extras.violations(password));
+        violations
+    }
+}
+
+impl<P: std::fmt::Display> std::fmt::Display for LinePolicy<P> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.policy)?;
+        for extra in &self.extras {
+            write!(f, "; {}", extra)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days_of_advent::day02::policies::range_password_policy::RangePasswordPolicy;
+
+    #[test]
+    fn extras_must_all_pass() {
+        let record = PolicyRecord {
+            numbers: (1, 3),
+            character: 'a',
+            password: "abc",
+            extras: vec![
+                ExtraPolicy::Length(LengthPasswordPolicy { range: std::ops::RangeInclusive::new(3, 8) }),
+                ExtraPolicy::MaxRun(MaxRunPasswordPolicy { max_run: 1 }),
+            ],
+        };
+
+        let policy = LinePolicy::<RangePasswordPolicy>::from_record(&record).unwrap();
+
+        assert!(policy.is_password_valid("abc"));
+        assert!(!policy.is_password_valid("ab"));
+        assert!(!policy.is_password_valid("abbc"));
+        assert_eq!(policy.to_string(), "1-3 a; length 3-8; run 1");
+        assert_eq!(
+            policy.violations("bb"),
+            [
+                Violation::Count { character: 'a', found: 0, expected: std::ops::RangeInclusive::new(1, 3) },
+                Violation::Length { found: 2, expected: std::ops::RangeInclusive::new(3, 8) },
+                Violation::Run { character: 'b', length: 2, max_run: 1 },
+            ]
+        );
+    }
+}
@@ -1,18 +1,84 @@
+use super::positions::{OutOfRange, Positions};
 use super::validates_password::ValidatesPassword;
+use super::violation::Violation;
+use crate::days_of_advent::day02::parsers::parse_error::Token;
+use crate::days_of_advent::day02::parsers::policy_record::{FromPolicyRecord, InvalidNumber, PolicyRecord};
 
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct IndexPasswordPolicy {
     pub indices: (usize, usize),
-    pub character: char
+    pub character: char,
+    /// How `indices` are read. Left out of serialized policies that use the
+    /// puzzle's one-based, lenient character positions
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Positions::is_default"))]
+    pub positions: Positions,
+}
+
+impl IndexPasswordPolicy {
+    /// The policy a record describes, reading its numbers as `positions`.
+    /// Numbers that cannot name a position, such as a one-based `0`, are
+    /// turned away here rather than when checking passwords
+    pub fn from_record_with(record: &PolicyRecord<'_>, positions: Positions) -> Result<Self, InvalidNumber> {
+        for (number, position) in [record.numbers.0, record.numbers.1].iter().enumerate() {
+            if !positions.accepts(*position) {
+                return Err(InvalidNumber { number, expected: Token::Position });
+            }
+        }
+        Ok(IndexPasswordPolicy {
+            indices: record.numbers,
+            character: record.character,
+            positions,
+        })
+    }
+
+    fn lookup(&self, password: &str) -> (Option<bool>, Option<bool>) {
+        self.positions.lookup(password, self.indices, self.character)
+    }
+}
+
+impl std::fmt::Display for IndexPasswordPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}-{} {}", self.indices.0, self.indices.1, self.character)
+    }
+}
+
+impl FromPolicyRecord for IndexPasswordPolicy {
+    fn from_record(record: &PolicyRecord<'_>) -> Result<Self, InvalidNumber> {
+        IndexPasswordPolicy::from_record_with(record, Positions::default())
+    }
 }
 
 impl ValidatesPassword for IndexPasswordPolicy {
     fn is_password_valid(&self, password: &str) -> bool {
-        let index0_char = password.chars().nth(self.indices.0 - 1);
-        let index1_char = password.chars().nth(self.indices.1 - 1);
+        match (self.lookup(password), self.positions.out_of_range) {
+            ((Some(first), Some(second)), _) => first != second,
+            ((None, None), OutOfRange::Lenient) => true,
+            (_, OutOfRange::Strict) => false,
+            ((first, second), _) => first.unwrap_or(false) != second.unwrap_or(false),
+        }
+    }
 
-        (index0_char.is_none() && index1_char.is_none()) || 
-        (index0_char.is_some() && index0_char.unwrap() == self.character) != 
-        (index1_char.is_some() && index1_char.unwrap() == self.character)
+    fn violations(&self, password: &str) -> Vec<Violation> {
+        if self.is_password_valid(password) {
+            return vec![];
+        }
+        let (character, positions) = (self.character, self.indices);
+        let (first, second) = self.lookup(password);
+        if self.positions.out_of_range == OutOfRange::Strict && (first.is_none() || second.is_none()) {
+            let length = self.positions.length(password);
+            let unit = self.positions.unit;
+            return [(positions.0, first), (positions.1, second)]
+                .iter()
+                .filter(|(_, holds)| holds.is_none())
+                .map(|(position, _)| Violation::OutOfRange { position: *position, length, unit })
+                .collect();
+        }
+        if first == Some(true) {
+            vec![Violation::BothPositions { character, positions }]
+        } else {
+            vec![Violation::NeitherPosition { character, positions }]
+        }
     }
 }
 
@@ -22,7 +88,7 @@ mod tests {
 
     #[test]
     fn test_0() {
-        let input = super::IndexPasswordPolicy { indices: (1, 2), character: 'a'};
+        let input = super::IndexPasswordPolicy { indices: (1, 2), character: 'a', positions: Default::default() };
         let password = "ab";
         let result = input.is_password_valid(&password);
 
@@ -31,7 +97,7 @@ mod tests {
 
     #[test]
     fn test_1() {
-        let input = super::IndexPasswordPolicy { indices: (1, 2), character: 'a'};
+        let input = super::IndexPasswordPolicy { indices: (1, 2), character: 'a', positions: Default::default() };
         let password = "a";
         let result = input.is_password_valid(&password);
 
@@ -40,10 +106,107 @@ mod tests {
 
     #[test]
     fn test_2() {
-        let input = super::IndexPasswordPolicy { indices: (1, 2), character: 'a'};
+        let input = super::IndexPasswordPolicy { indices: (1, 2), character: 'a', positions: Default::default() };
         let password = "b";
         let result = input.is_password_valid(&password);
 
         assert_eq!(result, false);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn explain_positions() {
+        let input = super::IndexPasswordPolicy { indices: (1, 3), character: 'a', positions: Default::default() };
+
+        assert_eq!(input.violations("abc"), vec![]);
+        assert_eq!(input.violations("aba")[0].to_string(), "positions 1 and 3 both contain 'a'");
+        assert_eq!(input.violations("bab")[0].to_string(), "neither position 1 nor 3 contains 'a'");
+    }
+
+    #[test]
+    fn zero_is_not_a_one_based_position() {
+        use super::FromPolicyRecord;
+        use crate::days_of_advent::day02::parsers::byte_password_policy_parser::BytePasswordPolicyParser;
+        use crate::days_of_advent::day02::parsers::password_policy_parser::PasswordPolicyParser;
+
+        let parsed: Result<(&str, super::IndexPasswordPolicy), _> = BytePasswordPolicyParser.parse("1-0 a: abc");
+        assert_eq!(
+            parsed.unwrap_err().to_string(),
+            "Line 1, column 3: expected a position of at least 1, found '0'"
+        );
+
+        let record = super::PolicyRecord { numbers: (0, 2), character: 'a', extras: vec![], password: "ab" };
+        assert!(super::IndexPasswordPolicy::from_record(&record).is_err());
+        let zero_based = super::IndexPasswordPolicy::from_record_with(&record, "zero".parse().unwrap()).unwrap();
+        assert!(zero_based.is_password_valid("ab"));
+        assert!(!zero_based.is_password_valid("aba"));
+    }
+
+    #[test]
+    fn out_of_range_is_explicit() {
+        let policy = |settings: &str| super::IndexPasswordPolicy {
+            indices: (4, 5),
+            character: 'a',
+            positions: settings.parse().unwrap(),
+        };
+
+        assert!(policy("lenient").is_password_valid("abc"));
+        assert!(!policy("missing").is_password_valid("abc"));
+        assert!(policy("missing").is_password_valid("abca"));
+        assert!(!policy("strict").is_password_valid("abca"));
+        assert_eq!(
+            policy("strict").violations("abca")[0].to_string(),
+            "position 5 is past the end of the password (4 chars)"
+        );
+        assert_eq!(policy("missing").violations("abc")[0].to_string(), "neither position 4 nor 5 contains 'a'");
+    }
+
+    #[test]
+    fn positions_count_graphemes() {
+        // "e" and a combining acute accent make one grapheme but two chars
+        let policy = |settings: &str| super::IndexPasswordPolicy {
+            indices: (2, 4),
+            character: 'a',
+            positions: settings.parse().unwrap(),
+        };
+
+        assert!(policy("grapheme").is_password_valid("e\u{301}ab"));
+        assert!(!policy("char").is_password_valid("e\u{301}ab"));
+        assert!(policy("char").is_password_valid("éab"));
+    }
+
+    #[test]
+    fn display_policy() {
+        let input = super::IndexPasswordPolicy { indices: (1, 3), character: 'a', positions: Default::default() };
+
+        assert_eq!(input.to_string(), "1-3 a");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn policy_round_trips_through_json() {
+        let policy = super::IndexPasswordPolicy { indices: (1, 3), character: 'a', positions: Default::default() };
+
+        let json = serde_json::to_string(&policy).unwrap();
+
+        assert_eq!(json, r#"{"indices":[1,3],"character":"a"}"#);
+        assert_eq!(serde_json::from_str::<super::IndexPasswordPolicy>(&json).unwrap(), policy);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn positions_are_serialized_when_set() {
+        let policy = super::IndexPasswordPolicy {
+            indices: (0, 3),
+            character: 'a',
+            positions: "zero,grapheme".parse().unwrap(),
+        };
+
+        let json = serde_json::to_string(&policy).unwrap();
+
+        assert_eq!(
+            json,
+            r#"{"indices":[0,3],"character":"a","positions":{"base":"Zero","unit":"Grapheme","out_of_range":"Lenient"}}"#
+        );
+        assert_eq!(serde_json::from_str::<super::IndexPasswordPolicy>(&json).unwrap(), policy);
+    }
+}
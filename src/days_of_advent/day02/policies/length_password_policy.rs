@@ -0,0 +1,45 @@
+use super::validates_password::ValidatesPassword;
+use super::violation::Violation;
+
+/// Valid when the password has between `min` and `max` characters
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LengthPasswordPolicy {
+    pub range: std::ops::RangeInclusive<usize>
+}
+
+impl std::fmt::Display for LengthPasswordPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "length {}-{}", self.range.start(), self.range.end())
+    }
+}
+
+impl ValidatesPassword for LengthPasswordPolicy {
+    fn is_password_valid(&self, password: &str) -> bool {
+        self.range.contains(&password.chars().count())
+    }
+
+    fn violations(&self, password: &str) -> Vec<Violation> {
+        let found = password.chars().count();
+        if self.range.contains(&found) {
+            return vec![];
+        }
+        vec![Violation::Length { found, expected: self.range.clone() }]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn length_counts_characters() {
+        let policy = LengthPasswordPolicy { range: std::ops::RangeInclusive::new(2, 3) };
+
+        assert!(!policy.is_password_valid("a"));
+        assert!(policy.is_password_valid("né"));
+        assert!(!policy.is_password_valid("abcd"));
+        assert_eq!(policy.to_string(), "length 2-3");
+        assert_eq!(policy.violations("abcd")[0].to_string(), "has 4 characters, expected 2..=3");
+    }
+}
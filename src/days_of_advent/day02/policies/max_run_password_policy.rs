@@ -0,0 +1,71 @@
+use super::validates_password::ValidatesPassword;
+use super::violation::Violation;
+
+/// Valid when no character is repeated more than `max_run` times in a row
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MaxRunPasswordPolicy {
+    pub max_run: usize
+}
+
+impl MaxRunPasswordPolicy {
+    /// The longest run of one character in the password
+    pub fn longest_run(password: &str) -> usize {
+        MaxRunPasswordPolicy::longest_run_of(password).map_or(0, |(_, run)| run)
+    }
+
+    /// The character of the first longest run in the password, and its length
+    fn longest_run_of(password: &str) -> Option<(char, usize)> {
+        let mut longest: Option<(char, usize)> = None;
+        let mut run = 0;
+        let mut previous = None;
+        for c in password.chars() {
+            run = if previous == Some(c) { run + 1 } else { 1 };
+            if longest.is_none_or(|(_, length)| run > length) {
+                longest = Some((c, run));
+            }
+            previous = Some(c);
+        }
+        longest
+    }
+}
+
+impl std::fmt::Display for MaxRunPasswordPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "run {}", self.max_run)
+    }
+}
+
+impl ValidatesPassword for MaxRunPasswordPolicy {
+    fn is_password_valid(&self, password: &str) -> bool {
+        MaxRunPasswordPolicy::longest_run(password) <= self.max_run
+    }
+
+    fn violations(&self, password: &str) -> Vec<Violation> {
+        match MaxRunPasswordPolicy::longest_run_of(password) {
+            Some((character, length)) if length > self.max_run => {
+                vec![Violation::Run { character, length, max_run: self.max_run }]
+            }
+            _ => vec![],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn runs_are_limited() {
+        let policy = MaxRunPasswordPolicy { max_run: 2 };
+
+        assert_eq!(MaxRunPasswordPolicy::longest_run("abbbcc"), 3);
+        assert_eq!(MaxRunPasswordPolicy::longest_run(""), 0);
+        assert!(policy.is_password_valid("aabbaa"));
+        assert!(!policy.is_password_valid("abbbc"));
+        assert_eq!(
+            policy.violations("abbbcccc")[0].to_string(),
+            "repeats 'c' 4 times in a row, expected at most 2"
+        );
+    }
+}
@@ -1,3 +1,13 @@
+pub mod banned_substring_password_policy;
+pub mod char_class_password_policy;
+pub mod combinators;
+pub mod extra_policy;
 pub mod index_password_policy;
+pub mod length_password_policy;
+pub mod max_run_password_policy;
+pub mod policy_rule;
+pub mod positions;
 pub mod range_password_policy;
-pub mod validates_password;
\ No newline at end of file
+pub mod regex_password_policy;
+pub mod validates_password;
+pub mod violation;
\ No newline at end of file
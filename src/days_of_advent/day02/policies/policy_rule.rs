@@ -0,0 +1,163 @@
+use super::combinators::CombinesPolicies;
+use super::index_password_policy::IndexPasswordPolicy;
+use super::positions::Positions;
+use super::range_password_policy::RangePasswordPolicy;
+use super::validates_password::ValidatesPassword;
+use crate::days_of_advent::day02::parsers::policy_record::{FromPolicyRecord, InvalidNumber, PolicyRecord};
+
+/// A rule combining the policies each line describes, such as
+/// `range and not index`. `not` binds tightest, then `and`, `xor` and `or`
+#[derive(Debug, PartialEq)]
+pub enum PolicyRule {
+    Range,
+    Index,
+    And(Box<PolicyRule>, Box<PolicyRule>),
+    Or(Box<PolicyRule>, Box<PolicyRule>),
+    Xor(Box<PolicyRule>, Box<PolicyRule>),
+    Not(Box<PolicyRule>),
+}
+
+impl PolicyRule {
+    /// The policy this rule makes of one line, reading index policies'
+    /// positions as `positions` says
+    pub fn policy(
+        &self,
+        record: &PolicyRecord<'_>,
+        positions: Positions,
+    ) -> Result<Box<dyn ValidatesPassword>, InvalidNumber> {
+        Ok(match self {
+            PolicyRule::Range => Box::new(RangePasswordPolicy::from_record(record)?),
+            PolicyRule::Index => Box::new(IndexPasswordPolicy::from_record_with(record, positions)?),
+            PolicyRule::And(a, b) => Box::new(a.policy(record, positions)?.and(b.policy(record, positions)?)),
+            PolicyRule::Or(a, b) => Box::new(a.policy(record, positions)?.or(b.policy(record, positions)?)),
+            PolicyRule::Xor(a, b) => Box::new(a.policy(record, positions)?.xor(b.policy(record, positions)?)),
+            PolicyRule::Not(a) => Box::new(a.policy(record, positions)?.not()),
+        })
+    }
+}
+
+impl std::str::FromStr for PolicyRule {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        let spaced = s.replace('(', " ( ").replace(')', " ) ");
+        let mut tokens = spaced.split_whitespace().peekable();
+
+        let rule = parse_or(&mut tokens, 0)?;
+        match tokens.next() {
+            None => Ok(rule),
+            Some(token) => Err(format!("Unexpected '{}' in rule '{}'", token, s)),
+        }
+    }
+}
+
+type Tokens<'a> = std::iter::Peekable<std::str::SplitWhitespace<'a>>;
+
+/// How many `not`s and parentheses a rule may nest, so that parsing, showing
+/// and applying it cannot run out of stack
+const MAX_DEPTH: usize = 64;
+
+/// Parse operands joined by `operator`, each parsed by `operand` at `depth`
+fn parse_binary(
+    tokens: &mut Tokens,
+    depth: usize,
+    operator: &str,
+    operand: fn(&mut Tokens, usize) -> Result<PolicyRule, String>,
+    combine: fn(Box<PolicyRule>, Box<PolicyRule>) -> PolicyRule,
+) -> Result<PolicyRule, String> {
+    let mut rule = operand(tokens, depth)?;
+    while tokens.next_if(|token| token.eq_ignore_ascii_case(operator)).is_some() {
+        rule = combine(Box::new(rule), Box::new(operand(tokens, depth)?));
+    }
+    Ok(rule)
+}
+
+fn parse_or(tokens: &mut Tokens, depth: usize) -> Result<PolicyRule, String> {
+    parse_binary(tokens, depth, "or", parse_xor, PolicyRule::Or)
+}
+
+fn parse_xor(tokens: &mut Tokens, depth: usize) -> Result<PolicyRule, String> {
+    parse_binary(tokens, depth, "xor", parse_and, PolicyRule::Xor)
+}
+
+fn parse_and(tokens: &mut Tokens, depth: usize) -> Result<PolicyRule, String> {
+    parse_binary(tokens, depth, "and", parse_not, PolicyRule::And)
+}
+
+fn parse_not(tokens: &mut Tokens, depth: usize) -> Result<PolicyRule, String> {
+    if depth > MAX_DEPTH {
+        return Err(format!("Rules may nest 'not' and '(' at most {} deep", MAX_DEPTH));
+    }
+    match tokens.next() {
+        Some(token) if token.eq_ignore_ascii_case("not") => {
+            Ok(PolicyRule::Not(Box::new(parse_not(tokens, depth + 1)?)))
+        }
+        Some(token) if token.eq_ignore_ascii_case("range") => Ok(PolicyRule::Range),
+        Some(token) if token.eq_ignore_ascii_case("index") => Ok(PolicyRule::Index),
+        Some("(") => {
+            let rule = parse_or(tokens, depth + 1)?;
+            match tokens.next() {
+                Some(")") => Ok(rule),
+                _ => Err("Expected ')' to close '('".to_string()),
+            }
+        }
+        Some(token) => Err(format!("Expected a policy, found '{}'", token)),
+        None => Err("Expected a policy, found the end of the rule".to_string()),
+    }
+}
+
+impl std::fmt::Display for PolicyRule {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            PolicyRule::Range => write!(f, "range"),
+            PolicyRule::Index => write!(f, "index"),
+            PolicyRule::And(a, b) => write!(f, "({} and {})", a, b),
+            PolicyRule::Or(a, b) => write!(f, "({} or {})", a, b),
+            PolicyRule::Xor(a, b) => write!(f, "({} xor {})", a, b),
+            PolicyRule::Not(a) => write!(f, "not {}", a),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_rule_with_precedence() {
+        let rule: PolicyRule = "range and not index or index".parse().unwrap();
+
+        assert_eq!(rule.to_string(), "((range and not index) or index)");
+        assert_eq!("NOT (range xor index)".parse::<PolicyRule>().unwrap().to_string(), "not (range xor index)");
+        assert_eq!("range and".parse::<PolicyRule>(), Err("Expected a policy, found the end of the rule".to_string()));
+        assert_eq!("(range".parse::<PolicyRule>(), Err("Expected ')' to close '('".to_string()));
+        assert_eq!("range index".parse::<PolicyRule>(), Err("Unexpected 'index' in rule 'range index'".to_string()));
+    }
+
+    #[test]
+    fn deeply_nested_rules_are_rejected() {
+        let too_deep = Err("Rules may nest 'not' and '(' at most 64 deep".to_string());
+        let nots = |n: usize| format!("{}range", "not ".repeat(n));
+        let parens = |n: usize| format!("{}range{}", "(".repeat(n), ")".repeat(n));
+
+        assert!(nots(64).parse::<PolicyRule>().is_ok());
+        assert!(parens(64).parse::<PolicyRule>().is_ok());
+        assert_eq!(nots(65).parse::<PolicyRule>(), too_deep);
+        assert_eq!(parens(65).parse::<PolicyRule>(), too_deep);
+        assert_eq!(nots(100_000).parse::<PolicyRule>(), too_deep);
+        assert_eq!(parens(100_000).parse::<PolicyRule>(), too_deep);
+    }
+
+    #[test]
+    fn rule_builds_policy_per_line() {
+        let record = PolicyRecord { numbers: (1, 3), character: 'a', extras: vec![], password: "aba" };
+        let rule: PolicyRule = "range and not index".parse().unwrap();
+
+        let policy = rule.policy(&record, Positions::default()).unwrap();
+        let zero_based = PolicyRule::Index.policy(&record, "zero".parse().unwrap()).unwrap();
+
+        assert!(policy.is_password_valid(record.password));
+        assert!(!policy.is_password_valid("abc"));
+        assert!(zero_based.is_password_valid("xab"));
+    }
+}
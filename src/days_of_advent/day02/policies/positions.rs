@@ -0,0 +1,238 @@
+use unicode_segmentation::UnicodeSegmentation;
+
+/// The number of the first position in a password
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Base {
+    #[default]
+    One,
+    Zero,
+}
+
+/// What a position counts through the password
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Unit {
+    /// Unicode scalar values, as `str::chars` gives them
+    #[default]
+    Char,
+    /// Extended grapheme clusters, what a reader sees as one character
+    Grapheme,
+    /// UTF-8 bytes. A position holds a character when the character's
+    /// encoding starts there
+    Byte,
+}
+
+/// How to treat a position past the end of the password
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum OutOfRange {
+    /// As the puzzle's solutions always have: a missing position does not
+    /// hold the character, but a password missing both positions is valid
+    #[default]
+    Lenient,
+    /// A missing position does not hold the character
+    Missing,
+    /// A password missing either position is invalid
+    Strict,
+}
+
+/// How an index policy reads its two positions
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Positions {
+    pub base: Base,
+    pub unit: Unit,
+    pub out_of_range: OutOfRange,
+}
+
+impl Positions {
+    #[cfg(feature = "serde")]
+    pub fn is_default(&self) -> bool {
+        *self == Positions::default()
+    }
+
+    /// Whether `position` can name a place in some password. Only `0` with
+    /// one-based positions cannot
+    pub fn accepts(&self, position: usize) -> bool {
+        self.offset(position).is_some()
+    }
+
+    /// Whether each position holds `character`, or `None` for a position
+    /// past the end of the password. ASCII passwords are looked up directly,
+    /// others in one walk through the password
+    pub fn lookup(&self, password: &str, positions: (usize, usize), character: char) -> (Option<bool>, Option<bool>) {
+        let (first, second) = (self.offset(positions.0), self.offset(positions.1));
+
+        let bytes_are_units = match self.unit {
+            Unit::Byte => true,
+            Unit::Char => password.is_ascii(),
+            // A carriage return and line feed are one grapheme
+            Unit::Grapheme => password.is_ascii() && !password.contains("\r\n"),
+        };
+        if bytes_are_units {
+            let look = |offset: Option<usize>| offset.and_then(|offset| self.holds(password, offset, character));
+            return (look(first), look(second));
+        }
+
+        let last = match first.max(second) {
+            Some(last) => last,
+            None => return (None, None),
+        };
+        let mut found = (None, None);
+        let mut note = |offset: usize, holds: bool| {
+            if Some(offset) == first {
+                found.0 = Some(holds);
+            }
+            if Some(offset) == second {
+                found.1 = Some(holds);
+            }
+        };
+        match self.unit {
+            Unit::Grapheme => {
+                for (offset, grapheme) in password.graphemes(true).enumerate().take(last + 1) {
+                    note(offset, is_character(grapheme, character));
+                }
+            }
+            _ => {
+                for (offset, c) in password.chars().enumerate().take(last + 1) {
+                    note(offset, c == character);
+                }
+            }
+        }
+        found
+    }
+
+    /// The number of units in the password
+    pub fn length(&self, password: &str) -> usize {
+        match self.unit {
+            Unit::Char => password.chars().count(),
+            Unit::Grapheme => password.graphemes(true).count(),
+            Unit::Byte => password.len(),
+        }
+    }
+
+    fn offset(&self, position: usize) -> Option<usize> {
+        match self.base {
+            Base::One => position.checked_sub(1),
+            Base::Zero => Some(position),
+        }
+    }
+
+    /// Whether the unit at `offset` holds the character, for passwords whose
+    /// units are their bytes
+    fn holds(&self, password: &str, offset: usize, character: char) -> Option<bool> {
+        if offset >= password.len() {
+            return None;
+        }
+        match self.unit {
+            Unit::Byte => Some(password.is_char_boundary(offset) && password[offset..].starts_with(character)),
+            _ => Some(password.as_bytes()[offset] as char == character),
+        }
+    }
+}
+
+fn is_character(grapheme: &str, character: char) -> bool {
+    let mut chars = grapheme.chars();
+    chars.next() == Some(character) && chars.next().is_none()
+}
+
+impl std::str::FromStr for Positions {
+    type Err = String;
+
+    /// Read settings such as `zero,grapheme,strict`. Settings not given keep
+    /// their defaults of `one`, `char` and `lenient`
+    fn from_str(s: &str) -> Result<Self, String> {
+        let mut positions = Positions::default();
+        for setting in s.split(',').map(|setting| setting.trim()) {
+            match setting.to_ascii_lowercase().as_str() {
+                "one" => positions.base = Base::One,
+                "zero" => positions.base = Base::Zero,
+                "char" => positions.unit = Unit::Char,
+                "grapheme" => positions.unit = Unit::Grapheme,
+                "byte" => positions.unit = Unit::Byte,
+                "lenient" => positions.out_of_range = OutOfRange::Lenient,
+                "missing" => positions.out_of_range = OutOfRange::Missing,
+                "strict" => positions.out_of_range = OutOfRange::Strict,
+                _ => {
+                    return Err(format!(
+                        "'{}' is not one of one, zero, char, grapheme, byte, lenient, missing or strict",
+                        setting
+                    ))
+                }
+            }
+        }
+        Ok(positions)
+    }
+}
+
+impl std::fmt::Display for Unit {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Unit::Char => write!(f, "char"),
+            Unit::Grapheme => write!(f, "grapheme"),
+            Unit::Byte => write!(f, "byte"),
+        }
+    }
+}
+
+impl std::fmt::Display for Positions {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let base = match self.base {
+            Base::One => "one",
+            Base::Zero => "zero",
+        };
+        let out_of_range = match self.out_of_range {
+            OutOfRange::Lenient => "lenient",
+            OutOfRange::Missing => "missing",
+            OutOfRange::Strict => "strict",
+        };
+        write!(f, "{},{},{}", base, self.unit, out_of_range)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn positions(s: &str) -> Positions {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn ascii_and_unicode_lookups_agree() {
+        let chars = Positions::default();
+
+        assert_eq!(chars.lookup("abc", (1, 3), 'a'), (Some(true), Some(false)));
+        assert_eq!(chars.lookup("éba", (1, 3), 'a'), (Some(false), Some(true)));
+        assert_eq!(chars.lookup("éb", (1, 3), 'é'), (Some(true), None));
+        assert_eq!(positions("zero").lookup("abc", (0, 2), 'c'), (Some(false), Some(true)));
+        assert_eq!(chars.lookup("abc", (0, 2), 'b'), (None, Some(true)));
+        assert_eq!(chars.lookup("ébc", (0, 2), 'b'), (None, Some(true)));
+    }
+
+    #[test]
+    fn units_count_differently() {
+        // "e" and a combining acute accent, then "x"
+        let password = "e\u{301}x";
+
+        assert_eq!(positions("char").lookup(password, (1, 3), 'x'), (Some(false), Some(true)));
+        assert_eq!(positions("grapheme").lookup(password, (1, 2), 'x'), (Some(false), Some(true)));
+        assert_eq!(positions("byte").lookup(password, (1, 4), 'x'), (Some(false), Some(true)));
+        assert_eq!(positions("byte").lookup("éa", (1, 2), 'é'), (Some(true), Some(false)));
+        assert_eq!(positions("grapheme").lookup("a\r\nb", (2, 3), 'b'), (Some(false), Some(true)));
+        assert_eq!(positions("grapheme").length(password), 2);
+    }
+
+    #[test]
+    fn settings_round_trip() {
+        assert_eq!(positions("zero, grapheme,STRICT").to_string(), "zero,grapheme,strict");
+        assert_eq!(positions("byte").to_string(), "one,byte,lenient");
+        assert!(!Positions::default().accepts(0));
+        assert!(positions("zero").accepts(0));
+        assert_eq!(
+            "first".parse::<Positions>(),
+            Err("'first' is not one of one, zero, char, grapheme, byte, lenient, missing or strict".to_string())
+        );
+    }
+}
\ No newline at end of file
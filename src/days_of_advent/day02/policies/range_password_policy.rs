@@ -1,15 +1,42 @@
 use super::validates_password::ValidatesPassword;
+use super::violation::Violation;
+use crate::days_of_advent::day02::parsers::policy_record::{FromPolicyRecord, InvalidNumber, PolicyRecord};
 
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RangePasswordPolicy {
     pub range: std::ops::RangeInclusive<usize>,
     pub character: char
 }
 
+impl std::fmt::Display for RangePasswordPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}-{} {}", self.range.start(), self.range.end(), self.character)
+    }
+}
+
+impl FromPolicyRecord for RangePasswordPolicy {
+    fn from_record(record: &PolicyRecord<'_>) -> Result<Self, InvalidNumber> {
+        Ok(RangePasswordPolicy {
+            range: std::ops::RangeInclusive::new(record.numbers.0, record.numbers.1),
+            character: record.character,
+        })
+    }
+}
+
 impl ValidatesPassword for RangePasswordPolicy {
     fn is_password_valid(&self, password: &str) -> bool {
         let num_matches = password.matches(self.character).count();
         self.range.contains(&num_matches)
     }
+
+    fn violations(&self, password: &str) -> Vec<Violation> {
+        let num_matches = password.matches(self.character).count();
+        if self.range.contains(&num_matches) {
+            return vec![];
+        }
+        vec![Violation::Count { character: self.character, found: num_matches, expected: self.range.clone() }]
+    }
 }
 
 #[cfg(test)]
@@ -51,4 +78,39 @@ mod tests {
 
         assert_eq!(result, false);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn explain_count() {
+        let policy = RangePasswordPolicy {
+            range: std::ops::RangeInclusive::new(1, 3),
+            character: 'a'
+        };
+
+        assert_eq!(policy.violations("aaa"), vec![]);
+        assert_eq!(policy.violations("aaaa")[0].to_string(), "found 4 'a', expected 1..=3");
+    }
+
+    #[test]
+    fn display_policy() {
+        let policy = RangePasswordPolicy {
+            range: std::ops::RangeInclusive::new(1, 3),
+            character: 'a'
+        };
+
+        assert_eq!(policy.to_string(), "1-3 a");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn policy_round_trips_through_json() {
+        let policy = RangePasswordPolicy {
+            range: std::ops::RangeInclusive::new(1, 3),
+            character: 'a'
+        };
+
+        let json = serde_json::to_string(&policy).unwrap();
+
+        assert_eq!(json, r#"{"range":{"start":1,"end":3},"character":"a"}"#);
+        assert_eq!(serde_json::from_str::<RangePasswordPolicy>(&json).unwrap(), policy);
+    }
+}
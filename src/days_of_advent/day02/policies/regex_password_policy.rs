@@ -0,0 +1,52 @@
+use regex::Regex;
+
+use super::validates_password::ValidatesPassword;
+use super::violation::Violation;
+
+/// Valid when the regex matches somewhere in the password. Anchor it with
+/// `^` and `$` to match the whole password
+#[derive(Debug, Clone)]
+pub struct RegexPasswordPolicy {
+    pub regex: Regex
+}
+
+/// Policies with the same pattern are the same policy
+impl PartialEq for RegexPasswordPolicy {
+    fn eq(&self, other: &Self) -> bool {
+        self.regex.as_str() == other.regex.as_str()
+    }
+}
+
+impl std::fmt::Display for RegexPasswordPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "regex {}", self.regex.as_str())
+    }
+}
+
+impl ValidatesPassword for RegexPasswordPolicy {
+    fn is_password_valid(&self, password: &str) -> bool {
+        self.regex.is_match(password)
+    }
+
+    fn violations(&self, password: &str) -> Vec<Violation> {
+        if self.regex.is_match(password) {
+            return vec![];
+        }
+        vec![Violation::NoMatch(self.regex.as_str().to_string())]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn regex_must_match() {
+        let policy = RegexPasswordPolicy { regex: Regex::new(r"^[a-z]+\d$").unwrap() };
+
+        assert!(policy.is_password_valid("hunter2"));
+        assert!(!policy.is_password_valid("hunter22"));
+        assert_eq!(policy.violations("hunter22")[0].to_string(), r"does not match the regex ^[a-z]+\d$");
+        assert_eq!(policy.to_string(), r"regex ^[a-z]+\d$");
+    }
+}
@@ -1,3 +1,46 @@
+use super::violation::Violation;
+
 pub trait ValidatesPassword {
     fn is_password_valid(&self, password: &str) -> bool;
+
+    /// Every reason the password fails the policy, empty exactly when
+    /// `is_password_valid` is true
+    fn violations(&self, password: &str) -> Vec<Violation>;
+
+    /// Every reason the password fails the negation of the policy, empty
+    /// exactly when `is_password_valid` is false. Negations override this, so
+    /// that a double negation explains itself with the policy inside
+    fn negated_violations(&self, password: &str) -> Vec<Violation> {
+        match self.is_password_valid(password) {
+            true => vec![Violation::Negated],
+            false => vec![],
+        }
+    }
+}
+
+/// Boxed policies validate like the policy inside, so rules chosen at run
+/// time can be combined
+impl<P: ValidatesPassword + ?Sized> ValidatesPassword for Box<P> {
+    fn is_password_valid(&self, password: &str) -> bool {
+        (**self).is_password_valid(password)
+    }
+
+    fn violations(&self, password: &str) -> Vec<Violation> {
+        (**self).violations(password)
+    }
+
+    fn negated_violations(&self, password: &str) -> Vec<Violation> {
+        (**self).negated_violations(password)
+    }
+}
+
+/// A list of policies is valid when every policy in it is
+impl<P: ValidatesPassword> ValidatesPassword for Vec<P> {
+    fn is_password_valid(&self, password: &str) -> bool {
+        self.iter().all(|policy| policy.is_password_valid(password))
+    }
+
+    fn violations(&self, password: &str) -> Vec<Violation> {
+        self.iter().flat_map(|policy| policy.violations(password)).collect()
+    }
 }
\ No newline at end of file
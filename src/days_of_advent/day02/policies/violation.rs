@@ -0,0 +1,111 @@
+use super::char_class_password_policy::CharClass;
+use super::positions::Unit;
+
+/// One reason a password fails a policy
+#[derive(Debug, Clone, PartialEq)]
+pub enum Violation {
+    /// The character appears too few or too many times
+    Count { character: char, found: usize, expected: std::ops::RangeInclusive<usize> },
+    /// Both positions hold the character, where only one may
+    BothPositions { character: char, positions: (usize, usize) },
+    /// Neither position holds the character
+    NeitherPosition { character: char, positions: (usize, usize) },
+    /// A position falls past the end of a password `length` units long
+    OutOfRange { position: usize, length: usize, unit: Unit },
+    /// The password is too short or too long
+    Length { found: usize, expected: std::ops::RangeInclusive<usize> },
+    /// No character of the class appears
+    MissingClass(CharClass),
+    /// A banned word appears, ignoring case
+    Banned(String),
+    /// The regex, given by its pattern, matches nowhere
+    NoMatch(String),
+    /// A character repeats more times in a row than allowed
+    Run { character: char, length: usize, max_run: usize },
+    /// The password passes a policy it must fail
+    Negated,
+    /// The password passes both policies, where exactly one must pass
+    BothPassed,
+}
+
+impl std::fmt::Display for Violation {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Violation::Count { character, found, expected } => {
+                write!(f, "found {} '{}', expected {:?}", found, character, expected)
+            }
+            Violation::BothPositions { character, positions } => {
+                write!(f, "positions {} and {} both contain '{}'", positions.0, positions.1, character)
+            }
+            Violation::NeitherPosition { character, positions } => {
+                write!(f, "neither position {} nor {} contains '{}'", positions.0, positions.1, character)
+            }
+            Violation::OutOfRange { position, length, unit } => {
+                write!(f, "position {} is past the end of the password ({} {}s)", position, length, unit)
+            }
+            Violation::Length { found, expected } => write!(f, "has {} characters, expected {:?}", found, expected),
+            Violation::MissingClass(class) => write!(f, "has no {} character", class),
+            Violation::Banned(word) => write!(f, "contains the banned word '{}'", word),
+            Violation::NoMatch(pattern) => write!(f, "does not match the regex {}", pattern),
+            Violation::Run { character, length, max_run } => {
+                write!(f, "repeats '{}' {} times in a row, expected at most {}", character, length, max_run)
+            }
+            Violation::Negated => write!(f, "passes a policy it must fail"),
+            Violation::BothPassed => write!(f, "passes both policies, where exactly one must pass"),
+        }
+    }
+}
+
+impl Violation {
+    /// The fewest single-character changes that would clear this violation
+    /// on its own, or `None` when no number of changes would, or there is no
+    /// simple count
+    pub fn edits(&self) -> Option<usize> {
+        match self {
+            Violation::Count { found, expected, .. } | Violation::Length { found, expected } => {
+                if expected.is_empty() {
+                    None
+                } else if found < expected.start() {
+                    Some(expected.start() - found)
+                } else {
+                    Some(found - expected.end())
+                }
+            }
+            Violation::BothPositions { .. } | Violation::NeitherPosition { .. } => Some(1),
+            Violation::MissingClass(_) | Violation::Banned(_) => Some(1),
+            Violation::Run { length, max_run, .. } => match max_run {
+                0 => None,
+                _ => Some(length / (max_run + 1)),
+            },
+            Violation::OutOfRange { .. } | Violation::NoMatch(_) | Violation::Negated | Violation::BothPassed => None,
+        }
+    }
+}
+
+/// Join violations into one reason, such as `found 4 'a', expected 1..=3;
+/// has 4 characters, expected 8..=20`
+pub fn describe_violations(violations: &[Violation]) -> String {
+    violations.iter().map(|violation| violation.to_string()).collect::<Vec<_>>().join("; ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn edits_to_clear_violations() {
+        let count = |found: usize, min: usize, max: usize| Violation::Count {
+            character: 'a',
+            found,
+            expected: std::ops::RangeInclusive::new(min, max),
+        };
+
+        assert_eq!(count(0, 2, 3).edits(), Some(2));
+        assert_eq!(count(7, 2, 3).edits(), Some(4));
+        assert_eq!(count(1, 3, 2).edits(), None);
+        assert_eq!(Violation::NeitherPosition { character: 'a', positions: (1, 3) }.edits(), Some(1));
+        assert_eq!(Violation::Run { character: 'a', length: 5, max_run: 2 }.edits(), Some(1));
+        assert_eq!(Violation::Run { character: 'a', length: 6, max_run: 2 }.edits(), Some(2));
+        assert_eq!(Violation::Negated.edits(), None);
+    }
+}
\ No newline at end of file
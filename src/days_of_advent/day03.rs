@@ -1,62 +1,66 @@
-use crate::days_of_advent::common::io;
+use crate::days_of_advent::common::io::{Grid, PuzzleInput};
+use crate::days_of_advent::common::puzzle::{Answer, Puzzle};
 
-pub fn run() -> () {
-    let puzzle_input = io::load_input_from_file("day03");
-    if puzzle_input.is_err() {
-        panic!("Could not load entries from file");
-    }
-    let puzzle_input = puzzle_input.unwrap();
+/// The Day 03 puzzle, counting the trees hit on each slope down the map
+pub struct TobogganTrajectory;
 
-    let slopes = [
-        (1,1),
-        (1,3),
-        (1,5),
-        (1,7),
-        (2,1)
-    ];
+impl Puzzle for TobogganTrajectory {
+    fn day(&self) -> usize {
+        3
+    }
 
-    let num_trees = count_trees(&puzzle_input, &(1,3));
+    fn title(&self) -> &'static str {
+        "Toboggan Trajectory"
+    }
 
-    let mult_trees : usize = slopes.iter().map(|s| count_trees(&puzzle_input, s)).product();
-    let content = format!("\
-        Found {} trees in our path for slope (1.3)\n\
-        For each slope, multiplied trees were {}", num_trees, mult_trees);
+    fn description(&self) -> &'static str {
+        "Count the trees on the slopes"
+    }
 
-    let report = io::format_day_report(3, "Toboggan Trajectory", "Count the trees on the slopes", &content);
-    println!("{}", report);
+    fn solve(&self, input: &str) -> Result<Vec<Answer>, String> {
+        let slopes = [
+            (1,1),
+            (1,3),
+            (1,5),
+            (1,7),
+            (2,1)
+        ];
+
+        let map = PuzzleInput::new(input).grid()?;
+
+        let num_trees = count_trees(&map, &(1,3));
+
+        let trees_per_slope : Vec<usize> = slopes.iter().map(|s| count_trees(&map, s)).collect();
+        let mult_trees : usize = trees_per_slope.iter().product();
+
+        let explanation = slopes
+            .iter()
+            .zip(&trees_per_slope)
+            .map(|(s, t)| format!("Down {}, right {} hits {} trees", s.0, s.1, t))
+            .collect();
+
+        Ok(vec![
+            Answer::new(num_trees, format!("Found {} trees in our path for slope (1.3)", num_trees)),
+            Answer::new(mult_trees, format!("For each slope, multiplied trees were {}", mult_trees))
+                .explained_by(explanation),
+        ])
+    }
 }
 
-fn count_trees(map: &str, slope: &(usize, usize)) -> usize {
+fn count_trees(map: &Grid, slope: &(usize, usize)) -> usize {
     let mut pos : (usize, usize) = (0, 0);
-
-    let num_columns = map.lines().next().unwrap().chars().count();
     let mut num_trees : usize = 0;
 
-    while pos.0 < map.lines().count() {
-        num_trees += if get_char_at_index(&map, pos).unwrap() == '#' {1} else {0};
-        pos = get_index_with_wraparound(&pos, &slope, num_columns);
+    while pos.0 < map.height() {
+        num_trees += if map.get(pos.0, pos.1) == Some('#') {1} else {0};
+        pos = get_index_with_wraparound(&pos, slope, map.width());
     }
 
     num_trees
 }
 
-fn get_char_at_index(slopes: &str, pos: (usize, usize)) -> Result<char, &str> {
-    if pos.0 >= slopes.lines().count() {
-        Err("Given row was out of bounds")
-    } else if pos.1 >= slopes.lines().next().unwrap().len() {
-        Err("Given column was out of bounds")
-    } else {
-        let character : char = slopes.lines().nth(pos.0).unwrap().chars().nth(pos.1).unwrap();
-        Ok(character)
-    }
-}
-
 fn get_index_with_wraparound(pos: &(usize, usize), offset: &(usize, usize), col_limit: usize) -> (usize, usize) {
-    let mut column = pos.1 + offset.1;
-    if column > (col_limit - 1) {
-        column -= col_limit;
-    }
-    (pos.0 + offset.0, column)
+    (pos.0 + offset.0, (pos.1 + offset.1) % col_limit)
 }
 
 #[cfg(test)]
@@ -79,7 +83,9 @@ mod tests {
         .#..#...#.#\
         ";
 
-        let num_trees = count_trees(&input, &(1,3));
+        let map = PuzzleInput::new(input).grid().unwrap();
+
+        let num_trees = count_trees(&map, &(1,3));
         assert_eq!(num_trees, 7);
     }
 
@@ -108,27 +114,27 @@ mod tests {
     }
 
     #[test]
-    fn test_get_char_at_index_1() {
+    fn test_grid_get_1() {
         let input = "\
         abdce\n\
         fghij\n\
         klmno\
         ";
 
-        let character = get_char_at_index(&input, (0,0)).unwrap();
-        assert_eq!(character, 'a');
+        let character = PuzzleInput::new(input).grid().unwrap().get(0,0);
+        assert_eq!(character, Some('a'));
     }
 
     #[test]
-    fn test_get_char_at_index_2() {
+    fn test_grid_get_2() {
         let input = "\
         abdce\n\
         fghij\n\
         klmno\
         ";
 
-        let character = get_char_at_index(&input, (2,4)).unwrap();
-        assert_eq!(character, 'o');
+        let character = PuzzleInput::new(input).grid().unwrap().get(2,4);
+        assert_eq!(character, Some('o'));
     }
 
 }
\ No newline at end of file
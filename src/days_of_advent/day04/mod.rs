@@ -1,4 +1,6 @@
-use crate::days_of_advent::common::io;
+use crate::days_of_advent::common::budget::Budget;
+use crate::days_of_advent::common::io::{self, PuzzleInput};
+use crate::days_of_advent::common::puzzle::{Answer, Puzzle};
 
 pub mod passport;
 pub mod passport_deserializer;
@@ -7,14 +9,67 @@ pub mod passport_validator;
 use passport_deserializer::PassportDeserializer;
 use passport_validator::PassportValidator;
 
-pub fn run() -> () {
-    let puzzle_input = io::load_input_from_file("day04");
-    if puzzle_input.is_err() {
-        panic!("Could not load entries from file");
+/// The Day 04 puzzle, counting the passports with the required and valid fields
+pub struct PassportProcessing;
+
+impl Puzzle for PassportProcessing {
+    fn day(&self) -> usize {
+        4
+    }
+
+    fn title(&self) -> &'static str {
+        "Passport Processing"
+    }
+
+    fn description(&self) -> &'static str {
+        "Count the number of valid passport"
     }
-    let puzzle_input = puzzle_input.unwrap();
 
-    let deserializer = passport_deserializer::BatchFilePassportDeserializer {
+    fn version(&self) -> &'static str {
+        "2"
+    }
+
+    fn solve(&self, input: &str) -> Result<Vec<Answer>, String> {
+        let deserializer = create_deserializer();
+        let strict_validator = create_validator();
+
+        let passports_w_required_fields = deserialize_passports(&PuzzleInput::new(input), &deserializer);
+        let num_passports_w_required_fields = passports_w_required_fields.len();
+
+        let passports_w_valid_fields = validate_passports(&passports_w_required_fields, &strict_validator);
+        let num_passports_w_valid_fields = passports_w_valid_fields.len();
+
+        Ok(answers(num_passports_w_required_fields, num_passports_w_valid_fields))
+    }
+
+    fn solve_reader(&self, reader: &mut dyn std::io::BufRead, budget: &Budget) -> Result<Vec<Answer>, String> {
+        let (num_passports_w_required_fields, num_passports_w_valid_fields) =
+            count_passports_in_records(budget.meter(io::records(reader)), &create_deserializer(), &create_validator())?;
+
+        Ok(answers(num_passports_w_required_fields, num_passports_w_valid_fields))
+    }
+}
+
+/// The answers for both parts, however the passports were counted
+fn answers(num_passports_w_required_fields: usize, num_passports_w_valid_fields: usize) -> Vec<Answer> {
+    vec![
+        Answer::new(
+            num_passports_w_required_fields,
+            format!("Found {} simply valid passports", num_passports_w_required_fields),
+        ),
+        Answer::new(
+            num_passports_w_valid_fields,
+            format!("and {} strictly valid passports", num_passports_w_valid_fields),
+        )
+        .explained_by(vec![format!(
+            "{} of the {} passports with required fields also have valid values",
+            num_passports_w_valid_fields, num_passports_w_required_fields
+        )]),
+    ]
+}
+
+fn create_deserializer() -> passport_deserializer::BatchFilePassportDeserializer {
+    passport_deserializer::BatchFilePassportDeserializer {
         required_fields: vec![
             "byr".to_string(),
             "iyr".to_string(),
@@ -24,9 +79,11 @@ pub fn run() -> () {
             "ecl".to_string(),
             "pid".to_string(),
         ],
-    };
+    }
+}
 
-    let strict_validator = passport_validator::StrictPassportValidator {
+fn create_validator() -> passport_validator::StrictPassportValidator {
+    passport_validator::StrictPassportValidator {
         birth_year_range: std::ops::RangeInclusive::new(1920, 2002),
         issue_year_range: std::ops::RangeInclusive::new(2010, 2020),
         expiration_year_range: std::ops::RangeInclusive::new(2020, 2030),
@@ -45,45 +102,42 @@ pub fn run() -> () {
         ]
         .to_vec(),
         passport_id_length: 9,
-    };
-
-    let passports_w_required_fields = deserialize_passports(&puzzle_input, &deserializer);
-    let num_passports_w_required_fields = passports_w_required_fields.len();
-
-    let passports_w_valid_fields = validate_passports(&passports_w_required_fields, &strict_validator);
-    let num_passports_w_valid_fields = passports_w_valid_fields.len();
-
-    let content = format!(
-        "\
-        Found {} simply valid passports\n\
-        and {} strictly valid passports",
-        num_passports_w_required_fields,
-        num_passports_w_valid_fields
-    );
-
-    let report = io::format_day_report(
-        4,
-        "Passport Processing",
-        "Count the number of valid passport",
-        &content,
-    );
-    println!("{}", report);
+    }
+}
+
+/// Count the passports with the required fields, and those that are also
+/// strictly valid, a record at a time so only one passport is in memory
+fn count_passports_in_records<S: AsRef<str>>(
+    records: impl Iterator<Item = Result<S, String>>,
+    deserializer: &impl PassportDeserializer,
+    validator: &impl PassportValidator,
+) -> Result<(usize, usize), String> {
+    let mut num_passports = (0, 0);
+    for record in records {
+        let record = record?.as_ref().replace('\n', " ");
+        if let Ok(passport) = deserializer.deserialize(&record) {
+            num_passports.0 += 1;
+            if validator.validate(&passport) == Ok(true) {
+                num_passports.1 += 1;
+            }
+        }
+    }
+    Ok(num_passports)
 }
 
 fn deserialize_passports(
-    batch_file: &str,
+    batch_file: &PuzzleInput,
     deserializer: &impl PassportDeserializer,
 ) -> Vec<passport::Passport> {
-    let serialized_passports = batch_file.replace('\n', " ");
-    let serialized_passports = serialized_passports.split("  ");
-
-    serialized_passports
-        .filter_map(|sp| deserializer.deserialize(&sp).ok())
+    batch_file
+        .groups()
+        .iter()
+        .filter_map(|group| deserializer.deserialize(&group.join(" ")).ok())
         .collect::<Vec<passport::Passport>>()
 }
 
 fn validate_passports<'a>(
-    passports: &'a Vec<passport::Passport>,
+    passports: &'a [passport::Passport],
     validator: &impl PassportValidator,
 ) -> Vec<&'a passport::Passport> {
     passports
@@ -114,7 +168,7 @@ mod tests {
         iyr:2011 ecl:brn hgt:59in\n\
         ";
 
-        let deserialized_passports = deserialize_passports(&input, &create_deserializer());
+        let deserialized_passports = deserialize_passports(&PuzzleInput::new(input), &create_deserializer());
         
         let num_passports_w_required_fields = deserialized_passports.len();
         assert_eq!(num_passports_w_required_fields, 2);
@@ -137,46 +191,36 @@ mod tests {
         iyr:2010 hgt:158cm hcl:#b6652a ecl:blu byr:1944 eyr:2021 pid:093154719\n\
         ";
 
-        let deserialized_passports = deserialize_passports(&input, &create_deserializer());
+        let deserialized_passports = deserialize_passports(&PuzzleInput::new(input), &create_deserializer());
         let valid_passports = validate_passports(&deserialized_passports, &create_validator());
 
         assert_eq!(valid_passports.len(), 4);
     }
 
-    fn create_deserializer() -> passport_deserializer::BatchFilePassportDeserializer {
-        passport_deserializer::BatchFilePassportDeserializer {
-            required_fields: vec![
-                "byr".to_string(),
-                "iyr".to_string(),
-                "eyr".to_string(),
-                "hgt".to_string(),
-                "hcl".to_string(),
-                "ecl".to_string(),
-                "pid".to_string(),
-            ],
-        }
-    }
+    #[test]
+    fn count_passports_in_records_matches_batch() {
+        let input = "\
+        ecl:gry pid:860033327 eyr:2020 hcl:#fffffd\r\n\
+        byr:1937 iyr:2017 cid:147 hgt:183cm\r\n\
+        \r\n\
+        iyr:2013 ecl:amb cid:350 eyr:2023 pid:028048884\r\n\
+        hcl:#cfa07d byr:1929\r\n\
+        \r\n\
+        hcl:#ae17e1 iyr:2013\r\n\
+        eyr:2024\r\n\
+        ecl:brn pid:760753108 byr:1931\r\n\
+        hgt:179cm\r\n\
+        \r\n\
+        hcl:#cfa07d eyr:2025 pid:166559648\r\n\
+        iyr:2011 ecl:brn hgt:59in\r\n\
+        ";
 
-    fn create_validator() -> passport_validator::StrictPassportValidator {
-        passport_validator::StrictPassportValidator {
-            birth_year_range: std::ops::RangeInclusive::new(1920, 2002),
-            issue_year_range: std::ops::RangeInclusive::new(2010, 2020),
-            expiration_year_range: std::ops::RangeInclusive::new(2020, 2030),
-            height_range: (
-                std::ops::RangeInclusive::new(150, 193),
-                std::ops::RangeInclusive::new(59, 76),
-            ),
-            eye_color: [
-                "amb".to_string(),
-                "blu".to_string(),
-                "brn".to_string(),
-                "gry".to_string(),
-                "grn".to_string(),
-                "hzl".to_string(),
-                "oth".to_string(),
-            ]
-            .to_vec(),
-            passport_id_length: 9,
-        }
+        let records = io::records(input.as_bytes());
+        let result = count_passports_in_records(records, &create_deserializer(), &create_validator()).unwrap();
+
+        assert_eq!(result, (2, 2));
+
+        let streamed = PassportProcessing.solve_reader(&mut input.as_bytes(), &Budget::unlimited());
+        assert_eq!(streamed, PassportProcessing.solve(input));
     }
 }
@@ -1,12 +1,14 @@
 use std::cmp;
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum HeightUnits {
     CM,
     IN,
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Height {
     pub value: u16,
     pub units: HeightUnits,
@@ -25,7 +27,7 @@ impl std::str::FromStr for Height {
 
         match height_scalar {
             Ok(value) => Ok(Height {
-                value: value,
+                value,
                 units: height_units,
             }),
             Err(_) => Err("Could not parse height as u16".to_string()),
@@ -34,6 +36,7 @@ impl std::str::FromStr for Height {
 }
 
 #[derive(cmp::PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct HexColor {
     pub r: u8,
     pub g: u8,
@@ -46,8 +49,10 @@ impl std::str::FromStr for HexColor {
     fn from_str(s: &str) -> Result<Self, String> {
         if !s.starts_with('#') {
             Err("String does not start with #".to_string())
-        } else if s.chars().count() != 7 {
+        } else if s.len() != 7 {
             Err("String has the wrong length".to_string())
+        } else if !s.is_ascii() {
+            Err("String has characters that are not hex digits".to_string())
         } else {
             let red_result = u8::from_str_radix(&s[1..=2], 16);
             if red_result.is_err() {
@@ -71,7 +76,8 @@ impl std::str::FromStr for HexColor {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Passport {
     pub birth_year: i32,
     pub issue_year: i32,
@@ -118,4 +124,31 @@ mod tests {
         let result = HexColor::from_str("#11111111");
         assert_eq!(result.is_err(), true);
     }
+
+    #[test]
+    fn parse_bad_hexcolor_from_string_4() {
+        use std::str::FromStr;
+        let result = HexColor::from_str("#aéaaa");
+        assert!(result.is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn passport_round_trips_through_json() {
+        use std::str::FromStr;
+        let passport = Passport {
+            birth_year: 1937,
+            issue_year: 2017,
+            expiration_year: 2020,
+            height: Height::from_str("183cm").unwrap(),
+            hair_color: HexColor::from_str("#fffffd").unwrap(),
+            eye_color: "gry".to_string(),
+            passport_id: "860033327".to_string(),
+        };
+
+        let json = serde_json::to_string(&passport).unwrap();
+
+        assert!(json.contains(r#""height":{"value":183,"units":"CM"}"#));
+        assert_eq!(serde_json::from_str::<Passport>(&json).unwrap(), passport);
+    }
 }
@@ -18,11 +18,10 @@ impl PassportDeserializer for BatchFilePassportDeserializer {
         let fields: std::collections::HashMap<&str, &str> = passport
             .trim()
             .split(' ')
-            .map(|f| BatchFilePassportDeserializer::parse_key_value_from_str(f))
-            .into_iter()
+            .map(BatchFilePassportDeserializer::parse_key_value_from_str)
             .collect();
 
-        return Ok(Passport {
+        Ok(Passport {
             birth_year: BatchFilePassportDeserializer::deserialize_value_from_map::<i32>(
                 "byr", &fields,
             )?,
@@ -40,7 +39,7 @@ impl PassportDeserializer for BatchFilePassportDeserializer {
             )?,
             eye_color: BatchFilePassportDeserializer::deserialize_value_from_map("ecl", &fields)?,
             passport_id: BatchFilePassportDeserializer::deserialize_value_from_map("pid", &fields)?,
-        });
+        })
     }
 }
 
@@ -1,68 +1,134 @@
-use crate::days_of_advent::common::io;
+use crate::days_of_advent::common::budget::Budget;
+use crate::days_of_advent::common::io::{self, PuzzleInput};
+use crate::days_of_advent::common::puzzle::{Answer, Puzzle};
 
-pub fn run() -> () {
-    let puzzle_input = io::load_input_from_file("day05");
-    if puzzle_input.is_err() {
-        panic!("Could not load entries from file");
+/// The Day 05 puzzle, decoding boarding passes into seat IDs
+pub struct BinaryBoarding;
+
+impl Puzzle for BinaryBoarding {
+    fn day(&self) -> usize {
+        5
+    }
+
+    fn title(&self) -> &'static str {
+        "Binary Boarding"
+    }
+
+    fn description(&self) -> &'static str {
+        "Find the highest seat ID"
+    }
+
+    fn version(&self) -> &'static str {
+        "2"
+    }
+
+    fn solve(&self, input: &str) -> Result<Vec<Answer>, String> {
+        let seat_map = SeatMap::from_boarding_passes(PuzzleInput::new(input).lines().map(Ok))?;
+        seat_map.answers()
     }
-    let puzzle_input = puzzle_input.unwrap();
 
-    let seat_ids = puzzle_input.lines().map(|l| seat_binary_to_id(&l)).collect::<Vec<u16>>();
-    let highest_seat_id = seat_ids.iter().max().unwrap();
+    fn solve_reader(&self, reader: &mut dyn std::io::BufRead, budget: &Budget) -> Result<Vec<Answer>, String> {
+        let seat_map = SeatMap::from_boarding_passes(budget.meter(io::lines(reader)))?;
+        seat_map.answers()
+    }
+}
 
-    let mut missing_seats: Vec<u16> = Vec::new();
+const NUM_SEATS: usize = 1024;
 
-    for seat_id in 0..*highest_seat_id as u16 {
-        if seat_id != 0u16 && ! seat_ids.contains(&seat_id) {
-            missing_seats.push(seat_id);
+/// Which of the plane's seats are taken. There are only `NUM_SEATS` possible
+/// IDs, so any number of boarding passes fits in a fixed amount of memory
+pub struct SeatMap {
+    taken: [bool; NUM_SEATS],
+}
+
+impl SeatMap {
+    pub fn from_boarding_passes<S: AsRef<str>>(
+        boarding_passes: impl Iterator<Item = Result<S, String>>,
+    ) -> Result<Self, String> {
+        let mut taken = [false; NUM_SEATS];
+        for (idx, boarding_pass) in boarding_passes.enumerate() {
+            let boarding_pass = boarding_pass?;
+            if !boarding_pass.as_ref().trim().is_empty() {
+                let id = seat_binary_to_id(boarding_pass.as_ref().trim())
+                    .map_err(|e| format!("Line {}: {}", idx + 1, e))?;
+                taken[id as usize] = true;
+            }
         }
+        Ok(SeatMap { taken })
     }
 
-    let my_seat = missing_seats.iter().filter(|s| seat_ids.contains(&(*s - 1)) && seat_ids.contains(&(*s+1))).collect::<Vec<&u16>>();
-    for &seat in &my_seat {
-        println!("Seat {} is empty and has neighbors", seat);
+    pub fn highest_seat_id(&self) -> Option<u16> {
+        self.taken.iter().rposition(|&t| t).map(|id| id as u16)
     }
 
-    let my_seat = my_seat[0];
-    let content = format!(
-        "\
-        Highest seat ID is {}\n\
-        My seat is {}",
-        highest_seat_id,
-        my_seat
-    );
+    /// Find the empty seats, below the highest taken one, whose neighbors
+    /// are both taken
+    pub fn empty_seats_with_neighbors(&self) -> Vec<u16> {
+        let highest_seat_id = self.highest_seat_id().unwrap_or(0) as usize;
+
+        (1..highest_seat_id)
+            .filter(|&id| !self.taken[id] && self.taken[id - 1] && self.taken[id + 1])
+            .map(|id| id as u16)
+            .collect()
+    }
+
+    fn answers(&self) -> Result<Vec<Answer>, String> {
+        let highest_seat_id = self.highest_seat_id().ok_or("No seats in input")?;
+
+        let empty_seats = self.empty_seats_with_neighbors();
+        let my_seat = empty_seats.first().ok_or("No empty seat has neighbors")?;
+
+        let explanation = empty_seats
+            .iter()
+            .map(|s| format!("Seat {} is empty and has neighbors {} and {}", s, s - 1, s + 1))
+            .collect();
 
-    let report = io::format_day_report(
-        5,
-        "Binary Boarding",
-        "Find the highest seat ID",
-        &content,
-    );
-    println!("{}", report);
+        Ok(vec![
+            Answer::new(highest_seat_id, format!("Highest seat ID is {}", highest_seat_id)),
+            Answer::new(my_seat, format!("My seat is {}", my_seat)).explained_by(explanation),
+        ])
+    }
 }
 
-fn seat_binary_to_id(binary_string: &str) -> u16 {
+/// The seat's ID, or what is wrong with the boarding pass
+fn seat_binary_to_id(binary_string: &str) -> Result<u16, String> {
     const ROW_CHARS : usize = 7;
     const COL_CHARS: usize = 3;
 
-    let mut itr = binary_string.chars();
+    let length = binary_string.chars().count();
+    if length != ROW_CHARS + COL_CHARS {
+        return Err(format!(
+            "'{}' has {} characters, but a boarding pass has {}",
+            binary_string,
+            length,
+            ROW_CHARS + COL_CHARS
+        ));
+    }
+
+    let mut itr = binary_string.chars().enumerate();
     let mut row: u16 = 0;
-    for idx in 0..ROW_CHARS {
-        row += match itr.nth(0).expect("Not F or B") {
+    for (idx, c) in itr.by_ref().take(ROW_CHARS) {
+        row += match c {
             'B' => 2u16.pow(ROW_CHARS as u32 - idx as u32 - 1),
-            _ => 0u16
+            'F' => 0u16,
+            _ => return Err(describe_misplaced(binary_string, idx, c, "F or B")),
         };
     }
 
     let mut col: u16 = 0;
-    for idx in (ROW_CHARS)..(ROW_CHARS + COL_CHARS) {
-        col += match itr.nth(0).expect("Not L or R") {
+    for (idx, c) in itr {
+        col += match c {
             'R' => 2u16.pow((ROW_CHARS + COL_CHARS) as u32 - idx as u32 - 1),
-            _ => 0u16
+            'L' => 0u16,
+            _ => return Err(describe_misplaced(binary_string, idx, c, "L or R")),
         };
     }
 
-    row * 8 + col
+    Ok(row * 8 + col)
+}
+
+fn describe_misplaced(binary_string: &str, idx: usize, c: char, expected: &str) -> String {
+    format!("'{}' has '{}' at column {}, where {} was expected", binary_string, c, idx + 1, expected)
 }
 
 #[cfg(test)]
@@ -73,7 +139,7 @@ mod test {
     fn acceptance_criteria_seat_id_1() {
         let input = "BFFFBBFRRR";
 
-        let result = seat_binary_to_id(&input);
+        let result = seat_binary_to_id(&input).unwrap();
 
         assert_eq!(result, 567);
     }
@@ -82,8 +148,47 @@ mod test {
     fn acceptance_criteria_seat_id_2() {
         let input = "BBFFBBFRLL";
 
-        let result = seat_binary_to_id(&input);
+        let result = seat_binary_to_id(&input).unwrap();
 
         assert_eq!(result, 820);
     }
+
+    #[test]
+    fn find_seat_between_neighbors() {
+        let mut seat_map = SeatMap { taken: [false; NUM_SEATS] };
+        for id in [3, 4, 5, 7, 8, 10] {
+            seat_map.taken[id] = true;
+        }
+
+        assert_eq!(seat_map.highest_seat_id(), Some(10));
+        assert_eq!(seat_map.empty_seats_with_neighbors(), [6, 9]);
+    }
+
+    #[test]
+    fn seat_map_from_boarding_passes() {
+        let input = "FFFFFFBLLL\r\nFFFFFFBLLR\r\nFFFFFFBLRR\r\n";
+
+        let seat_map = SeatMap::from_boarding_passes(io::lines(input.as_bytes())).unwrap();
+
+        assert_eq!(seat_map.highest_seat_id(), Some(11));
+        assert_eq!(seat_map.empty_seats_with_neighbors(), [10]);
+    }
+
+    #[test]
+    fn malformed_boarding_passes_are_errors() {
+        let boarding_passes = |input: &'static str| SeatMap::from_boarding_passes(io::lines(input.as_bytes())).err();
+
+        assert_eq!(
+            boarding_passes("FFFFFFBLLL\n\nFFXFFFBLLR\n"),
+            Some("Line 3: 'FFXFFFBLLR' has 'X' at column 3, where F or B was expected".to_string())
+        );
+        assert_eq!(
+            boarding_passes("FFFFFFBLLB\n"),
+            Some("Line 1: 'FFFFFFBLLB' has 'B' at column 10, where L or R was expected".to_string())
+        );
+        assert_eq!(
+            boarding_passes("FFFB\n"),
+            Some("Line 1: 'FFFB' has 4 characters, but a boarding pass has 10".to_string())
+        );
+    }
 }
\ No newline at end of file
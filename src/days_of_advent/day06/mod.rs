@@ -1,59 +1,59 @@
-use crate::days_of_advent::common::io;
+use crate::days_of_advent::common::io::PuzzleInput;
+use crate::days_of_advent::common::puzzle::{Answer, Puzzle};
 
-pub fn run() -> () {
-    let puzzle_input = io::load_input_from_file("day06");
-    if puzzle_input.is_err() {
-        panic!("Could not load entries from file");
+/// The Day 06 puzzle, counting the questions each group answered yes to
+pub struct CustomCustoms;
+
+impl Puzzle for CustomCustoms {
+    fn day(&self) -> usize {
+        6
+    }
+
+    fn title(&self) -> &'static str {
+        "Custom Customs"
     }
-    let puzzle_input = puzzle_input.unwrap();
-
-    let groups = split_groups(&puzzle_input);
-    let total_any = groups.iter().map(|g| count_unique_letters(&g.to_string())).sum::<usize>();
-    let total_all = groups.iter().map(|g| count_consistent_letters(&g.to_string())).sum::<usize>();
-
-    let content = format!(
-        "\
-        Total answers are {}\n\
-        Total consistent answers are {}",
-        total_any,
-        total_all
-    );
-
-    let report = io::format_day_report(
-        6,
-        "Custom Customs",
-        "Count group's yes answers",
-        &content,
-    );
-    println!("{}", report);
-}
 
-fn count_unique_letters(group_response: &str) -> usize {
-    let individual_responses = group_response.lines();
+    fn description(&self) -> &'static str {
+        "Count group's yes answers"
+    }
+
+    fn solve(&self, input: &str) -> Result<Vec<Answer>, String> {
+        let input = PuzzleInput::new(input);
+        let groups = input.groups();
+        let total_any = groups.iter().map(|g| count_unique_letters(g)).sum::<usize>();
+        let total_all = groups.iter().map(|g| count_consistent_letters(g)).sum::<usize>();
+
+        Ok(vec![
+            Answer::new(total_any, format!("Total answers are {}", total_any))
+                .explained_by(vec![format!("Summed the questions anyone answered yes to across {} groups", groups.len())]),
+            Answer::new(total_all, format!("Total consistent answers are {}", total_all))
+                .explained_by(vec![format!("Summed the questions everyone answered yes to across {} groups", groups.len())]),
+        ])
+    }
+}
 
-    let mut sorted = individual_responses.collect::<Vec<&str>>().concat().chars().collect::<Vec<char>>();
+fn count_unique_letters(group_response: &[&str]) -> usize {
+    let mut sorted = group_response.concat().chars().collect::<Vec<char>>();
     sorted.sort();
     sorted.dedup();
 
     sorted.len()
 }
 
-fn count_consistent_letters(group_response: &str) -> usize {
-    let shortest_line = group_response.clone().lines().min_by(|l1, l2| l1.len().cmp(&l2.len())).unwrap();
+fn count_consistent_letters(group_response: &[&str]) -> usize {
+    let shortest_line = match group_response.iter().min_by(|l1, l2| l1.len().cmp(&l2.len())) {
+        Some(line) => line,
+        None => return 0,
+    };
     let mut num_yes = 0;
     for character in shortest_line.chars() {
-        num_yes += if group_response.lines().all(|l| l.contains(character)) {1} else {0};
+        num_yes += if group_response.iter().all(|l| l.contains(character)) {1} else {0};
     }
 
     num_yes
 
 }
 
-fn split_groups(all_groups: &str) -> Vec<String> {
-    all_groups.split("\n\n").map(|s| s.to_string()).collect::<Vec<String>>()
-
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -77,15 +77,18 @@ mod tests {
         \n\
         b";
 
-        let groups = split_groups(&input);
-        let total = groups.iter().map(|g| count_unique_letters(&g.to_string())).sum::<usize>();
+        let input = PuzzleInput::new(input);
+        let groups = input.groups();
+        let total = groups.iter().map(|g| count_unique_letters(g)).sum::<usize>();
+        let total_consistent = groups.iter().map(|g| count_consistent_letters(g)).sum::<usize>();
 
-        assert_eq!(total, 11)
+        assert_eq!(total, 11);
+        assert_eq!(total_consistent, 6);
     }
 
     #[test]
     fn acceptance_test_1() {
-        let input = "abc";
+        let input = ["abc"];
 
         let num_yes = count_unique_letters(&input);
 
@@ -94,11 +97,9 @@ mod tests {
 
     #[test]
     fn acceptance_test_2() {
-        let input = "\
-        ab\n\
-        ac";
+        let input = PuzzleInput::new("ab\r\nac\r\n");
 
-        let num_yes = count_unique_letters(&input);
+        let num_yes = count_unique_letters(&input.groups()[0]);
 
         assert_eq!(num_yes, 3);
     }
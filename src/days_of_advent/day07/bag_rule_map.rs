@@ -1,4 +1,5 @@
 use super::bag_rules::*;
+use crate::days_of_advent::common::io::PuzzleInput;
 
 pub type BagRuleMap<'a> = std::collections::HashMap<&'a str, BagRule<'a>>;
 
@@ -13,11 +14,13 @@ impl BagRuleMapDeserializer {
         }
     }
 
-    pub fn deserialize<'a>(&self, data: &'a str) -> BagRuleMap<'a> {
+    pub fn deserialize<'a>(&self, data: &'a PuzzleInput) -> Result<BagRuleMap<'a>, String> {
         data.lines()
-            .map(|l| {
-                let bag_rule = self.bag_deserializer.deserialize(&l);
-                (bag_rule.id, bag_rule)
+            .enumerate()
+            .filter(|(_, l)| !l.trim().is_empty())
+            .map(|(idx, l)| {
+                let bag_rule = self.bag_deserializer.deserialize(l).map_err(|e| format!("Line {}: {}", idx + 1, e))?;
+                Ok((bag_rule.id, bag_rule))
             })
             .collect()
     }
@@ -42,7 +45,8 @@ mod tests {
 
         let bag_rule_map_deserializer = BagRuleMapDeserializer::new();
 
-        let bag_rules = bag_rule_map_deserializer.deserialize(&input);
+        let input = PuzzleInput::new(input);
+        let bag_rules = bag_rule_map_deserializer.deserialize(&input).unwrap();
 
         assert_eq!(9, bag_rules.len());
     }
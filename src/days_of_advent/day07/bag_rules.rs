@@ -1,9 +1,41 @@
 #[derive(Debug,PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct BagRule<'a> {
     pub id: &'a str,
     pub contents: Vec<(usize, &'a str)>,
 }
 
+/// A `BagRule` that owns its bag names, so that it can be deserialised and
+/// kept after the data it came from is gone. The solver itself only borrows
+#[cfg(feature = "serde")]
+#[allow(dead_code)]
+#[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct OwnedBagRule {
+    pub id: String,
+    pub contents: Vec<(usize, String)>,
+}
+
+#[cfg(feature = "serde")]
+#[allow(dead_code)]
+impl OwnedBagRule {
+    pub fn as_bag_rule(&self) -> BagRule<'_> {
+        BagRule {
+            id: &self.id,
+            contents: self.contents.iter().map(|(num, id)| (*num, id.as_str())).collect(),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<&BagRule<'_>> for OwnedBagRule {
+    fn from(rule: &BagRule) -> Self {
+        OwnedBagRule {
+            id: rule.id.to_string(),
+            contents: rule.contents.iter().map(|(num, id)| (*num, id.to_string())).collect(),
+        }
+    }
+}
+
 pub struct BagRuleDeserializer {
     line_regex: regex::Regex,
     single_bag_regex: regex::Regex,
@@ -13,47 +45,52 @@ impl BagRuleDeserializer {
     pub fn new() -> Self {
         BagRuleDeserializer {
             line_regex: regex::Regex::new(
-                r"(?P<container_bag_type>.*) bags contain (?P<contained_bag_types>.*).",
+                r"^(?P<container_bag_type>.+) bags contain (?P<contained_bag_types>.+)\.$",
             )
             .unwrap(),
             single_bag_regex: regex::Regex::new(
-                r"(?P<contained_bag_count>\d+) (?P<contained_bag_type>.*) bags?",
+                r"^(?P<contained_bag_count>\d+) (?P<contained_bag_type>.+) bags?$",
             )
             .unwrap(),
         }
     }
 
-    pub fn deserialize<'a>(&self, data: &'a str) -> BagRule<'a> {
-        let captures = self.line_regex.captures(data).unwrap();
+    /// Read one rule, or say which part of it could not be read
+    pub fn deserialize<'a>(&self, data: &'a str) -> Result<BagRule<'a>, String> {
+        let data = data.trim();
+        let captures = self.line_regex.captures(data).ok_or(format!(
+            "'{}' is not a rule such as 'light red bags contain 1 bright white bag, 2 muted yellow bags.'",
+            data
+        ))?;
         let id = captures.name("container_bag_type").unwrap().as_str();
-        let captures = captures.name("contained_bag_types");
-        let matched_text = captures.unwrap().as_str();
+        let matched_text = captures.name("contained_bag_types").unwrap().as_str();
         match matched_text {
-            "no other bags" => BagRule {
+            "no other bags" => Ok(BagRule {
                 id,
                 contents: vec![],
-            },
-            _ => BagRule {
+            }),
+            _ => Ok(BagRule {
                 id,
                 contents: matched_text
                     .split(",")
                     .map(|s| self.deserialize_one_contained_bag(s.trim()))
-                    .collect::<Vec<(usize, &'a str)>>(),
-            },
+                    .collect::<Result<Vec<(usize, &'a str)>, String>>()?,
+            }),
         }
     }
 
-    fn deserialize_one_contained_bag<'a>(&self, string: &'a str) -> (usize, &'a str) {
-        let captures = self.single_bag_regex.captures(string).unwrap();
-        (
+    fn deserialize_one_contained_bag<'a>(&self, string: &'a str) -> Result<(usize, &'a str), String> {
+        let not_bags = || format!("'{}' is not a number of bags such as '2 muted yellow bags'", string);
+        let captures = self.single_bag_regex.captures(string).ok_or_else(not_bags)?;
+        Ok((
             captures
                 .name("contained_bag_count")
                 .unwrap()
                 .as_str()
                 .parse::<usize>()
-                .unwrap(),
+                .map_err(|_| not_bags())?,
             captures.name("contained_bag_type").unwrap().as_str(),
-        )
+        ))
     }
 }
 
@@ -82,7 +119,7 @@ mod tests {
             ],
         };
         let bag_rule_deserializer = BagRuleDeserializer::new();
-        let result = bag_rule_deserializer.deserialize(&input);
+        let result = bag_rule_deserializer.deserialize(&input).unwrap();
 
         assert_eq!(result, solution);
     }
@@ -96,9 +133,40 @@ mod tests {
             contents: vec![],
         };
         let bag_rule_deserializer = BagRuleDeserializer::new();
-        let result = bag_rule_deserializer.deserialize(&input);
+        let result = bag_rule_deserializer.deserialize(&input).unwrap();
 
         assert_eq!(result, solution);
     }
 
-}
\ No newline at end of file
+    #[test]
+    fn malformed_rules_are_errors() {
+        let bag_rule_deserializer = BagRuleDeserializer::new();
+
+        assert_eq!(
+            bag_rule_deserializer.deserialize("light red bags hold no other bags."),
+            Err("'light red bags hold no other bags.' is not a rule such as \
+                 'light red bags contain 1 bright white bag, 2 muted yellow bags.'"
+                .to_string())
+        );
+        assert_eq!(
+            bag_rule_deserializer.deserialize("light red bags contain 1 bright white bag, some muted yellow bags."),
+            Err("'some muted yellow bags' is not a number of bags such as '2 muted yellow bags'".to_string())
+        );
+    }
+
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn bag_rules_round_trip_through_json() {
+        let rule = BagRuleDeserializer::new()
+            .deserialize("light red bags contain 1 bright white bag, 2 muted yellow bags.")
+            .unwrap();
+
+        let json = serde_json::to_string(&rule).unwrap();
+        let owned: OwnedBagRule = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(json, r#"{"id":"light red","contents":[[1,"bright white"],[2,"muted yellow"]]}"#);
+        assert_eq!(owned, OwnedBagRule::from(&rule));
+        assert_eq!(owned.as_bag_rule(), rule);
+    }
+}
@@ -1,115 +1,186 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet, VecDeque};
 
-use crate::days_of_advent::common::io;
+use crate::days_of_advent::common::budget::Budget;
+use crate::days_of_advent::common::io::PuzzleInput;
+use crate::days_of_advent::common::puzzle::{Answer, Puzzle};
 
 mod bag_rule_map;
 mod bag_rules;
 
-pub fn run() {
-    let puzzle_input = io::load_input_from_file("day07");
+/// The Day 07 puzzle, following the rules for which bags hold which
+pub struct HandyHaversacks;
 
-    let puzzle_input = puzzle_input.unwrap();
-
-    let bag_rule_map_deserializer = bag_rule_map::BagRuleMapDeserializer::new();
-    let total =
-        calc_num_bags_can_contain_bag(&puzzle_input, &bag_rule_map_deserializer, "shiny gold");
+impl Puzzle for HandyHaversacks {
+    fn day(&self) -> usize {
+        7
+    }
 
-    let total_bags_within =
-        calc_num_bags_inside_bag(&puzzle_input, &bag_rule_map_deserializer, "shiny gold");
+    fn title(&self) -> &'static str {
+        "Handy Haversacks"
+    }
 
-    let content = format!(
-        "\
-        Total number of options are {}\n\
-        Total number of contained bags are {}",
-        total, total_bags_within
-    );
+    fn description(&self) -> &'static str {
+        "Count bags that can contain a shiny gold bag"
+    }
 
-    let report = io::format_day_report(
-        7,
-        "Handy Haversacks",
-        "Count bags that can contain a shiny gold bag",
-        &content,
-    );
+    fn version(&self) -> &'static str {
+        "3"
+    }
 
-    println!("{}", report);
-}
+    fn solve(&self, input: &str) -> Result<Vec<Answer>, String> {
+        self.solve_within(input, &Budget::unlimited())
+    }
 
-pub fn calc_num_bags_can_contain_bag(
-    serialized_bag_rules: &str,
-    bag_rule_deserializer: &bag_rule_map::BagRuleMapDeserializer,
-    bag_type: &str,
-) -> usize {
-    let nodes: bag_rule_map::BagRuleMap = bag_rule_deserializer.deserialize(&serialized_bag_rules);
+    fn solve_within(&self, input: &str, budget: &Budget) -> Result<Vec<Answer>, String> {
+        let bag_rule_map_deserializer = bag_rule_map::BagRuleMapDeserializer::new();
+        let input = PuzzleInput::new(input);
+        let nodes = bag_rule_map_deserializer.deserialize(&input)?;
 
-    let mut cached_rule_distance: HashMap<String, Option<usize>> = HashMap::new();
+        let chains = find_containment_chains(&nodes, "shiny gold", budget)?;
+        let total = chains.len();
 
-    let mut total = 0;
+        let counts = count_bags_within("shiny gold", &nodes, budget)?;
+        let total_bags_within = counts["shiny gold"];
+        let contents = nodes["shiny gold"]
+            .contents
+            .iter()
+            .map(|(num, id)| format!("{} {} bags, each holding {} more", num, id, counts[id]))
+            .collect();
 
-    for (id, _) in &nodes {
-        let res = dist_from_node(id, bag_type, &nodes, &mut cached_rule_distance);
-        if res.is_some() && res.unwrap() != 0 {
-            total += 1;
-        }
+        Ok(vec![
+            Answer::new(total, format!("Total number of options are {}", total))
+                .explained_by(chains.iter().map(|chain| chain.join(" -> ")).collect()),
+            Answer::new(total_bags_within, format!("Total number of contained bags are {}", total_bags_within))
+                .explained_by(contents),
+        ])
     }
-
-    return total;
 }
 
-pub fn calc_num_bags_inside_bag(
-    serialized_bag_rules: &str,
-    bag_rule_deserializer: &bag_rule_map::BagRuleMapDeserializer,
-    bag_type: &str,
-) -> usize {
-    let nodes: bag_rule_map::BagRuleMap = bag_rule_deserializer.deserialize(&serialized_bag_rules);
+/// For every bag that can eventually hold `target_id`, the shortest chain of
+/// bags leading down to it, sorted by the outermost bag. Each chain follows
+/// the first bag held that is one step closer to `target_id`
+pub fn find_containment_chains<'a>(
+    map: &bag_rule_map::BagRuleMap<'a>,
+    target_id: &str,
+    budget: &Budget,
+) -> Result<Vec<Vec<&'a str>>, String> {
+    let distances = container_distances(map, target_id, budget)?;
 
-    num_bags_within_bag(bag_type, 1, &nodes) - 1
+    let distance_to = |id: &str| if id == target_id { Some(0) } else { distances.get(id).copied() };
+    let mut chains = vec![];
+    for (&id, &distance) in &distances {
+        let mut chain = vec![id];
+        for remaining in (0..distance).rev() {
+            let next = map[chain.last().unwrap()]
+                .contents
+                .iter()
+                .map(|(_, child_id)| *child_id)
+                .find(|child_id| distance_to(child_id) == Some(remaining))
+                .unwrap();
+            chain.push(next);
+        }
+        chains.push(chain);
+    }
+    chains.sort();
+    Ok(chains)
 }
 
-pub fn dist_from_node(
-    source_id: &str,
+/// How many bags down `target_id` is inside each bag that can eventually
+/// hold it, from one breadth-first search outwards through the bags that
+/// hold it. Each bag reached spends a step of `budget`
+fn container_distances<'a>(
+    map: &bag_rule_map::BagRuleMap<'a>,
     target_id: &str,
-    map: &bag_rule_map::BagRuleMap,
-    cached_map: &mut HashMap<String, Option<usize>>,
-) -> Option<usize> {
-    if cached_map.contains_key(source_id) {
-        return cached_map.get(source_id).unwrap().clone();
-    } else if source_id == target_id {
-        cached_map.insert(String::from(target_id), Some(0));
-        return Some(0);
-    } else if map.get(source_id).unwrap().contents.is_empty() {
-        return None;
-    } else {
-        let mut best_result = None;
-        for (_, child_node_name) in &map.get(source_id).unwrap().contents {
-            let child_node = map.get(child_node_name).unwrap();
-            let res = dist_from_node(child_node.id, target_id, map, cached_map);
-            cached_map.insert(String::from(child_node.id), res.clone());
-            if res == None {
-            } else if res.is_some() {
-                let num = res.unwrap();
-                if best_result.is_none() || best_result.clone().unwrap() > num {
-                    best_result = Some(num + 1);
-                }
+    budget: &Budget,
+) -> Result<HashMap<&'a str, usize>, String> {
+    let mut containers: HashMap<&str, Vec<&'a str>> = HashMap::new();
+    for rule in map.values() {
+        for (_, child_id) in &rule.contents {
+            containers.entry(child_id).or_default().push(rule.id);
+        }
+    }
+
+    let mut distances: HashMap<&'a str, usize> = HashMap::new();
+    let mut queue = VecDeque::from(vec![(target_id, 0)]);
+    while let Some((id, distance)) = queue.pop_front() {
+        budget.step()?;
+        for container_id in containers.get(id).map(Vec::as_slice).unwrap_or_default() {
+            if *container_id != target_id && !distances.contains_key(container_id) {
+                distances.insert(container_id, distance + 1);
+                queue.push_back((container_id, distance + 1));
             }
         }
-        return best_result;
     }
+    Ok(distances)
 }
 
-pub fn num_bags_within_bag(source_id: &str, num: usize, map: &bag_rule_map::BagRuleMap) -> usize {
-    let start_rule = map.get(source_id).unwrap();
-    let mut total = num;
-    for (num_child, id) in &start_rule.contents {
-        total += num * num_bags_within_bag(id, *num_child, &map);
+/// How many bags `source_id` holds, and so each bag found inside it. Rules
+/// are followed depth first with an explicit stack, so long chains of bags
+/// cannot overflow the call stack. Each rule followed spends a step of
+/// `budget`, and bags that end up inside themselves are an error
+pub fn count_bags_within<'a>(
+    source_id: &'a str,
+    map: &bag_rule_map::BagRuleMap<'a>,
+    budget: &Budget,
+) -> Result<HashMap<&'a str, usize>, String> {
+    let mut counts: HashMap<&'a str, usize> = HashMap::new();
+    // The bags being counted, outermost first, each with how many of its
+    // contents have been visited
+    let mut enclosing: Vec<(&'a str, usize)> = vec![];
+    let mut enclosing_ids: HashSet<&'a str> = HashSet::new();
+
+    let mut next = Some(source_id);
+    while let Some(id) = next.take() {
+        budget.step()?;
+        if !enclosing_ids.insert(id) {
+            let mut cycle: Vec<&str> = enclosing.iter().map(|(enclosing_id, _)| *enclosing_id).collect();
+            cycle.push(id);
+            return Err(format!("Bags end up inside themselves: {}", cycle.join(" -> ")));
+        }
+        map.get(id).ok_or(format!("No rule for {} bags", id))?;
+        enclosing.push((id, 0));
+
+        while let Some((id, visited)) = enclosing.last_mut() {
+            let id = *id;
+            let contents = &map[id].contents;
+            while *visited < contents.len() && counts.contains_key(contents[*visited].1) {
+                *visited += 1;
+            }
+            if let Some((_, child_id)) = contents.get(*visited) {
+                next = Some(child_id);
+                break;
+            }
+
+            let mut total: usize = 0;
+            for (num_child, child_id) in contents {
+                total = counts[child_id]
+                    .checked_add(1)
+                    .and_then(|n| n.checked_mul(*num_child))
+                    .and_then(|n| n.checked_add(total))
+                    .ok_or("Too many bags to count")?;
+            }
+            counts.insert(id, total);
+            enclosing_ids.remove(id);
+            enclosing.pop();
+        }
     }
 
-    return total;
+    Ok(counts)
 }
 
 #[cfg(test)]
 mod tests {
 
     use super::*;
+    use crate::days_of_advent::common::budget::BudgetLimits;
+
+    fn num_containers(nodes: &bag_rule_map::BagRuleMap, bag_type: &str, budget: &Budget) -> Result<usize, String> {
+        container_distances(nodes, bag_type, budget).map(|distances| distances.len())
+    }
+
+    fn num_inside(nodes: &bag_rule_map::BagRuleMap, bag_type: &str, budget: &Budget) -> Result<usize, String> {
+        count_bags_within(bag_type, nodes, budget).map(|counts| counts[bag_type])
+    }
 
     #[test]
     fn acceptance_criteria() {
@@ -125,9 +196,18 @@ mod tests {
         dotted black bags contain no other bags.";
 
         let bag_rule_map_deserializer = bag_rule_map::BagRuleMapDeserializer::new();
+        let input = PuzzleInput::new(input);
 
-        let total = calc_num_bags_can_contain_bag(&input, &bag_rule_map_deserializer, "shiny gold");
+        let budget = Budget::unlimited();
+        let nodes = bag_rule_map_deserializer.deserialize(&input).unwrap();
+
+        let total = num_containers(&nodes, "shiny gold", &budget).unwrap();
         assert_eq!(total, 4);
+
+        let chains = find_containment_chains(&nodes, "shiny gold", &budget).unwrap();
+        assert_eq!(chains.len(), total);
+        assert_eq!(chains[0], ["bright white", "shiny gold"]);
+        assert_eq!(chains[2], ["light red", "bright white", "shiny gold"]);
     }
 
     #[test]
@@ -142,8 +222,70 @@ mod tests {
         dark violet bags contain no other bags.";
 
         let bag_rule_map_deserializer = bag_rule_map::BagRuleMapDeserializer::new();
+        let input = PuzzleInput::new(input);
+
+        let nodes = bag_rule_map_deserializer.deserialize(&input).unwrap();
+
+        let total = num_inside(&nodes, "shiny gold", &Budget::unlimited());
+        assert_eq!(total, Ok(126));
+    }
+
+    #[test]
+    fn cyclic_rules_are_an_error() {
+        let input = "\
+        shiny gold bags contain 2 dark red bags.\n\
+        dark red bags contain 1 dark orange bag.\n\
+        dark orange bags contain 3 shiny gold bags.";
+
+        let bag_rule_map_deserializer = bag_rule_map::BagRuleMapDeserializer::new();
+        let input = PuzzleInput::new(input);
+        let budget = Budget::unlimited();
+        let nodes = bag_rule_map_deserializer.deserialize(&input).unwrap();
+
+        let total = num_containers(&nodes, "shiny gold", &budget);
+        let within = num_inside(&nodes, "shiny gold", &budget);
+
+        assert_eq!(total, Ok(2));
+        assert_eq!(
+            within,
+            Err("Bags end up inside themselves: shiny gold -> dark red -> dark orange -> shiny gold".to_string())
+        );
+    }
+
+    #[test]
+    fn counting_bags_respects_budget() {
+        let input = "\
+        shiny gold bags contain 2 dark red bags.\n\
+        dark red bags contain 2 dark orange bags.\n\
+        dark orange bags contain no other bags.";
+
+        let bag_rule_map_deserializer = bag_rule_map::BagRuleMapDeserializer::new();
+        let input = PuzzleInput::new(input);
+        let budget = BudgetLimits { max_steps: Some(2), ..Default::default() }.start();
+        let nodes = bag_rule_map_deserializer.deserialize(&input).unwrap();
+
+        let total = num_inside(&nodes, "shiny gold", &budget);
+
+        assert_eq!(total, Err("Budget exceeded: took more than 2 steps".to_string()));
+    }
+
+    #[test]
+    fn long_chains_of_bags() {
+        let depth = 50_000;
+        let mut input = String::from("shiny gold bags contain 1 bag 0 bag.\n");
+        for idx in 0..depth - 1 {
+            input += &format!("bag {} bags contain 1 bag {} bag.\n", idx, idx + 1);
+        }
+        input += &format!("bag {} bags contain no other bags.\n", depth - 1);
+        input += &format!("outer bags contain 2 bag {} bags.\n", depth - 1);
+
+        let input = PuzzleInput::new(&input);
+        let nodes = bag_rule_map::BagRuleMapDeserializer::new().deserialize(&input).unwrap();
+        let budget = Budget::unlimited();
 
-        let total = calc_num_bags_inside_bag(&input, &bag_rule_map_deserializer, "shiny gold");
-        assert_eq!(total, 126);
+        assert_eq!(num_inside(&nodes, "shiny gold", &budget), Ok(depth));
+        assert_eq!(num_inside(&nodes, "outer", &budget), Ok(2));
+        assert_eq!(num_containers(&nodes, "bag 0", &budget), Ok(1));
+        assert_eq!(num_containers(&nodes, &format!("bag {}", depth - 1), &budget), Ok(depth + 1));
     }
 }
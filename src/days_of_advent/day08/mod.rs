@@ -1,52 +1,122 @@
-use regex;
+use crate::days_of_advent::common::budget::Budget;
+use crate::days_of_advent::common::io::PuzzleInput;
+use crate::days_of_advent::common::puzzle::{Answer, Puzzle};
 
-use crate::days_of_advent::common::io;
+/// The Day 08 puzzle, running the boot code until it would loop forever
+pub struct HandheldHalting;
 
-pub fn run() {
-    let puzzle_input = io::load_input_from_file("day08");
+impl Puzzle for HandheldHalting {
+    fn day(&self) -> usize {
+        8
+    }
+
+    fn title(&self) -> &'static str {
+        "Handheld Halting"
+    }
 
-    let puzzle_input = puzzle_input.unwrap();
+    fn description(&self) -> &'static str {
+        "Determine value of accumulator"
+    }
 
-    let boot_code_deserializer = BootCodeDeserializer::new();
+    fn version(&self) -> &'static str {
+        "2"
+    }
 
-    let program = boot_code_deserializer.deserialize(&puzzle_input);
+    fn solve(&self, input: &str) -> Result<Vec<Answer>, String> {
+        self.solve_within(input, &Budget::unlimited())
+    }
 
-    let mut visited_indices = vec![];
-    let mut idx = 0;
-    let mut accumulated_value = 0;
+    fn solve_within(&self, input: &str, budget: &Budget) -> Result<Vec<Answer>, String> {
+        let boot_code_deserializer = BootCodeDeserializer::new();
 
-    while ! visited_indices.contains(&idx) {
-        visited_indices.push(idx);
-        match program[idx].0 {
-            InstructionCode::NoOperation => idx += 1,
-            InstructionCode::Jump => idx = (idx as i32 + program[idx].1) as usize,
-            InstructionCode::Accumulate => {accumulated_value += program[idx].1; idx += 1}
-        }
+        let program = boot_code_deserializer.deserialize(&PuzzleInput::new(input))?;
 
+        let boot_run = run_until_loop(&program, budget)?;
+
+        Ok(vec![
+            Answer::new(boot_run.accumulated_value, format!("Accumulated value is {}", boot_run.accumulated_value))
+                .explained_by(vec![format!(
+                    "Instruction {} ({}) would run a second time after {} instructions",
+                    boot_run.loop_start + 1,
+                    describe_instruction(&program[boot_run.loop_start]),
+                    boot_run.num_executed
+                )]),
+        ])
     }
+}
+
+/// The state of the boot code when an instruction was about to run twice
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BootRun {
+    pub accumulated_value: i32,
+    pub loop_start: usize,
+    pub num_executed: usize,
+}
+
+/// Run the boot code until an instruction is about to run a second time,
+/// spending a step of `budget` on each instruction. Fails if the program
+/// ends or jumps outside itself instead of looping
+pub fn run_until_loop(program: &[(InstructionCode, i32)], budget: &Budget) -> Result<BootRun, String> {
+    let mut visited = vec![false; program.len()];
+    let mut num_executed = 0;
+    let mut idx: i64 = 0;
+    let mut accumulated_value: i32 = 0;
+
+    loop {
+        if idx == program.len() as i64 {
+            return Err(format!("Boot code finished without looping after {} instructions", num_executed));
+        }
+        if idx < 0 || idx > program.len() as i64 {
+            return Err(format!("Boot code jumped to instruction {}, outside the program", idx + 1));
+        }
+        let current = idx as usize;
+        if visited[current] {
+            break;
+        }
+        budget.step()?;
+        visited[current] = true;
+        num_executed += 1;
 
-    let content = format!(
-        "\
-        Accumulated value is {}",
-        accumulated_value
-    );
+        match program[current].0 {
+            InstructionCode::NoOperation => idx += 1,
+            InstructionCode::Jump => idx += program[current].1 as i64,
+            InstructionCode::Accumulate => {
+                accumulated_value = accumulated_value
+                    .checked_add(program[current].1)
+                    .ok_or("Accumulator overflowed")?;
+                idx += 1
+            }
+        }
+    }
 
-    let report = io::format_day_report(
-        8,
-        "Handheld Halting",
-        "Determine value of accumulator",
-        &content,
-    );
+    Ok(BootRun {
+        accumulated_value,
+        loop_start: idx as usize,
+        num_executed,
+    })
+}
 
-    println!("{}", report);
+fn describe_instruction(instruction: &(InstructionCode, i32)) -> String {
+    let code = match instruction.0 {
+        InstructionCode::NoOperation => "nop",
+        InstructionCode::Accumulate => "acc",
+        InstructionCode::Jump => "jmp",
+    };
+    format!("{} {:+}", code, instruction.1)
 }
 
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum InstructionCode {
     NoOperation,
     Accumulate,
     Jump
 }
 
+/// Boot code, as each instruction and its argument in order
+pub type Program = Vec<(InstructionCode, i32)>;
+
 pub struct BootCodeDeserializer {
     line_regex: regex::Regex
 }
@@ -54,30 +124,48 @@ pub struct BootCodeDeserializer {
 impl BootCodeDeserializer {
     pub fn new() -> Self {
         BootCodeDeserializer {
-            line_regex: regex::Regex::new(r"(?P<instruction>\w+) (?P<sign>\+|-)(?P<value>\d+)").unwrap()
+            line_regex: regex::Regex::new(r"^(?P<instruction>\w+) (?P<value>[+-]\d+)$").unwrap()
         }
     }
 
-    pub fn deserialize(&self, data: &str) -> Vec<(InstructionCode, i32)> {
-        
-        return data.trim().lines().map(|l| self.deserialize_single_line(&l)).collect::<Vec<(InstructionCode, i32)>>();
+    /// Read each instruction, failing on the first line that is not one
+    pub fn deserialize(&self, data: &PuzzleInput) -> Result<Program, String> {
+        data.lines()
+            .enumerate()
+            .filter(|(_, l)| !l.trim().is_empty())
+            .map(|(idx, l)| self.deserialize_single_line(idx + 1, l))
+            .collect()
     }
 
-    fn deserialize_single_line(&self, line: &str) -> (InstructionCode, i32) {
-        let captures = self.line_regex.captures(&line).unwrap();
-        let instruction = match captures.name("instruction").unwrap().as_str() {
+    fn deserialize_single_line(&self, line_number: usize, line: &str) -> Result<(InstructionCode, i32), String> {
+        let indent = line.len() - line.trim_start().len();
+        let captures = self.line_regex.captures(line.trim()).ok_or(format!(
+            "Line {}: expected an instruction such as 'acc +1', found '{}'",
+            line_number,
+            line.trim()
+        ))?;
+        let column = |m: regex::Match| line[..indent + m.start()].chars().count() + 1;
+
+        let code = captures.name("instruction").unwrap();
+        let instruction = match code.as_str() {
             "nop" => InstructionCode::NoOperation,
             "acc" => InstructionCode::Accumulate,
             "jmp" => InstructionCode::Jump,
-            _ => panic!("Unexpected instruction code")
-        };
-        let unsigned_value = captures.name("value").unwrap().as_str().parse::<i32>().unwrap();
-        let value = match captures.name("sign").unwrap().as_str() {
-            "-" => -unsigned_value,
-            _ => unsigned_value
+            _ => {
+                return Err(format!(
+                    "Line {}, column {}: '{}' is not one of nop, acc or jmp",
+                    line_number,
+                    column(code),
+                    code.as_str()
+                ))
+            }
         };
+        let value = captures.name("value").unwrap();
+        let value = value.as_str().parse::<i32>().map_err(|_| {
+            format!("Line {}, column {}: '{}' does not fit in an i32", line_number, column(value), value.as_str())
+        })?;
 
-        return (instruction, value);
+        Ok((instruction, value))
     }
 
 }
@@ -85,6 +173,7 @@ impl BootCodeDeserializer {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::days_of_advent::common::budget::BudgetLimits;
 
     #[test]
     fn deserialize_input() {
@@ -102,6 +191,74 @@ mod tests {
 
         let deserializer = BootCodeDeserializer::new();
 
-        let result = deserializer.deserialize(&input);
+        let result = deserializer.deserialize(&PuzzleInput::new(input));
+    }
+
+    #[test]
+    fn malformed_instructions_are_errors() {
+        let deserializer = BootCodeDeserializer::new();
+        let deserialize = |input: &str| deserializer.deserialize(&PuzzleInput::new(input));
+
+        assert_eq!(
+            deserialize("nop +0\n\nbogus"),
+            Err("Line 3: expected an instruction such as 'acc +1', found 'bogus'".to_string())
+        );
+        assert_eq!(deserialize("  hop +1"), Err("Line 1, column 3: 'hop' is not one of nop, acc or jmp".to_string()));
+        assert_eq!(
+            deserialize("jmp +99999999999"),
+            Err("Line 1, column 5: '+99999999999' does not fit in an i32".to_string())
+        );
+    }
+
+    #[test]
+    fn run_until_loop_finds_entry_point() {
+        let input = "\
+        nop +0\n\
+        acc +1\n\
+        jmp +4\n\
+        acc +3\n\
+        jmp -3\n\
+        acc -99\n\
+        acc +1\n\
+        jmp -4\n\
+        acc +6\
+        ";
+
+        let program = BootCodeDeserializer::new().deserialize(&PuzzleInput::new(input)).unwrap();
+        let result = run_until_loop(&program, &Budget::unlimited()).unwrap();
+
+        assert_eq!(result, BootRun { accumulated_value: 5, loop_start: 1, num_executed: 7 });
+        assert_eq!(describe_instruction(&program[result.loop_start]), "acc +1");
+    }
+
+    #[test]
+    fn run_until_loop_respects_budget() {
+        let program = BootCodeDeserializer::new().deserialize(&PuzzleInput::new("nop +0\nacc +1\njmp -2")).unwrap();
+        let budget = BudgetLimits { max_steps: Some(2), ..Default::default() }.start();
+
+        assert_eq!(run_until_loop(&program, &budget), Err("Budget exceeded: took more than 2 steps".to_string()));
+    }
+
+    #[test]
+    fn run_until_loop_rejects_programs_that_escape() {
+        let deserializer = BootCodeDeserializer::new();
+        let budget = Budget::unlimited();
+
+        let finished = run_until_loop(&deserializer.deserialize(&PuzzleInput::new("nop +0\nacc +1")).unwrap(), &budget);
+        let escaped = run_until_loop(&deserializer.deserialize(&PuzzleInput::new("nop +0\njmp -5")).unwrap(), &budget);
+
+        assert_eq!(finished, Err("Boot code finished without looping after 2 instructions".to_string()));
+        assert_eq!(escaped, Err("Boot code jumped to instruction -3, outside the program".to_string()));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn program_round_trips_through_json() {
+        let program = BootCodeDeserializer::new().deserialize(&PuzzleInput::new("nop +0\nacc +1\njmp -2")).unwrap();
+
+        let json = serde_json::to_string(&program).unwrap();
+
+        assert_eq!(json, r#"[["NoOperation",0],["Accumulate",1],["Jump",-2]]"#);
+        assert_eq!(serde_json::from_str::<Program>(&json).unwrap(), program);
     }
 }
@@ -6,4 +6,20 @@ pub mod day04;
 pub mod day05;
 pub mod day06;
 pub mod day07;
-pub mod day08;
\ No newline at end of file
+pub mod day08;
+
+use common::puzzle::Puzzle;
+
+/// Every registered day of advent, in order. `new-day` adds to this list
+pub fn puzzles() -> Vec<Box<dyn Puzzle>> {
+    vec![
+        Box::new(day01::RepairReport),
+        Box::new(day02::PasswordPhilosophy),
+        Box::new(day03::TobogganTrajectory),
+        Box::new(day04::PassportProcessing),
+        Box::new(day05::BinaryBoarding),
+        Box::new(day06::CustomCustoms),
+        Box::new(day07::HandyHaversacks),
+        Box::new(day08::HandheldHalting),
+    ]
+}
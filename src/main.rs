@@ -1,12 +1,165 @@
+// Tests from before clippy ran on every change are kept as they were written
+#![cfg_attr(
+    test,
+    allow(
+        unused_variables,
+        clippy::needless_borrow,
+        clippy::bool_assert_comparison,
+        clippy::assertions_on_constants
+    )
+)]
+
+mod cli;
 mod days_of_advent;
 
+use days_of_advent::common::budget::BudgetLimits;
+use days_of_advent::common::{batch, io, runner, scaffold, server};
+
 fn main() {
-    days_of_advent::day01::run();
-    days_of_advent::day02::run();
-    days_of_advent::day03::run();
-    days_of_advent::day04::run();
-    days_of_advent::day05::run();
-    days_of_advent::day06::run();
-    days_of_advent::day07::run();
-    days_of_advent::day08::run();
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let command = match cli::parse_args(&args) {
+        Ok(command) => command,
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, cli::USAGE);
+            std::process::exit(2);
+        }
+    };
+
+    let result = match command {
+        cli::Command::Run { days, options } => run_days(&days, &options),
+        cli::Command::Batch { day, dir, budget } => run_batch(day, dir, budget),
+        cli::Command::Serve { address, budget } => {
+            let options = runner::RunOptions { budget, ..Default::default() };
+            server::serve(&address, &days_of_advent::puzzles(), &options)
+        }
+        cli::Command::Addends { input, sum, k, mode, width } => read_input_or_stored(input, "day01")
+            .and_then(|input| days_of_advent::day01::report_addends(&input, &sum, k, mode, width))
+            .map(|report| println!("{}", report)),
+        cli::Command::Subset { input, sum, min_size, max_size, witnesses, width } => read_input_or_stored(input, "day01")
+            .and_then(|input| {
+                days_of_advent::day01::report_subsets(&input, &sum, min_size, max_size, witnesses, width)
+            })
+            .map(|report| println!("{}", report)),
+        cli::Command::Passwords { input, rule, positions, explain } => read_input_or_stored(input, "day02")
+            .and_then(|input| days_of_advent::day02::report_rule(&input, &rule, positions, explain))
+            .map(|report| println!("{}", report)),
+        cli::Command::NormalizePasswords { input, output } => {
+            read_input_or_stored(input, "day02").and_then(|input| normalize_passwords(&input, output))
+        }
+        cli::Command::AuditPasswords { input } => read_input_or_stored(input, "day02")
+            .and_then(|input| days_of_advent::day02::report_audit(&input))
+            .map(|report| println!("{}", report)),
+        cli::Command::GeneratePasswords { rule, policy, length, alphabet, seed, count, valid } => {
+            generate_passwords(&rule, policy.as_deref(), length, &alphabet, seed, count, valid)
+                .map(|file| print!("{}", file))
+        }
+        cli::Command::BenchParsers { lines, seed } => {
+            days_of_advent::day02::report_parser_throughput(lines, seed).map(|report| println!("{}", report))
+        }
+        cli::Command::NewDay { day, title, description } => {
+            let description = description.unwrap_or_else(|| title.clone());
+            scaffold::new_day(day, &title, &description).map(|paths| {
+                for path in paths {
+                    println!("Wrote {}", path.display());
+                }
+            })
+        }
+    };
+
+    if let Err(e) = result {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    }
+}
+
+fn find_puzzle(day: usize) -> Result<Box<dyn days_of_advent::common::puzzle::Puzzle>, String> {
+    days_of_advent::puzzles()
+        .into_iter()
+        .find(|p| p.day() == day)
+        .ok_or(format!("Day {} has not been solved yet", day))
+}
+
+fn run_batch(day: usize, dir: Option<String>, budget: BudgetLimits) -> Result<(), String> {
+    let puzzle = find_puzzle(day)?;
+    let dir = match dir {
+        Some(dir) => std::path::PathBuf::from(dir),
+        None => io::share_dir(&puzzle.name()).join("inputs"),
+    };
+
+    let inputs = io::load_inputs_from_dir(&dir)?;
+    if inputs.is_empty() {
+        return Err(format!("No *.txt inputs in {}", dir.display()));
+    }
+
+    let results = batch::run_batch(puzzle.as_ref(), inputs, budget);
+    println!("{}", batch::render_batch_table(&results));
+
+    match results.iter().filter(|r| !r.is_ok()).count() {
+        0 => Ok(()),
+        failures => Err(format!("{} input(s) failed", failures)),
+    }
+}
+
+/// Write a password file in its canonical form to `output`, or stdout, and
+/// say which lines could not be read
+fn normalize_passwords(input: &str, output: Option<String>) -> Result<(), String> {
+    let (normalized, rejected) = days_of_advent::day02::normalize_password_file(input);
+    for e in &rejected {
+        eprintln!("Kept as is: {}", e);
+    }
+
+    match output {
+        Some(path) => std::fs::write(&path, normalized).map_err(|e| format!("Could not write {}: {}", path, e)),
+        None => {
+            print!("{}", normalized);
+            Ok(())
+        }
+    }
+}
+
+/// The given input file, or the day's stored input
+fn read_input_or_stored(input: Option<String>, day: &str) -> Result<String, String> {
+    match input {
+        Some(path) => std::fs::read_to_string(&path).map_err(|e| format!("Could not read {}: {}", path, e)),
+        None => io::load_input_from_file(day),
+    }
+}
+
+fn run_days(days: &[usize], options: &runner::RunOptions) -> Result<(), String> {
+    let puzzles = days_of_advent::puzzles();
+    if let Some(day) = days.iter().find(|d| !puzzles.iter().any(|p| p.day() == **d)) {
+        return Err(format!("Day {} has not been solved yet", day));
+    }
+
+    let mut failures = 0;
+    for puzzle in puzzles.iter().filter(|p| days.is_empty() || days.contains(&p.day())) {
+        match runner::run_puzzle(puzzle.as_ref(), options) {
+            Ok(report) => println!("{}", report),
+            Err(e) => {
+                eprintln!("Day {}: {}", puzzle.day(), e);
+                failures += 1;
+            }
+        }
+    }
+
+    match failures {
+        0 => Ok(()),
+        _ => Err(format!("{} day(s) failed to run", failures)),
+    }
+}
+
+/// A password file of `count` lines, `valid` of which pass the rule
+fn generate_passwords(
+    rule: &str,
+    policy: Option<&str>,
+    length: usize,
+    alphabet: &str,
+    seed: u64,
+    count: usize,
+    valid: usize,
+) -> Result<String, String> {
+    let rule: days_of_advent::day02::policies::policy_rule::PolicyRule = rule.parse()?;
+    let policy = policy.map(days_of_advent::day02::parse_policy).transpose()?;
+    let mut generator = days_of_advent::day02::PasswordGenerator::new(length, alphabet, seed)?;
+    generator.generate_file(&rule, policy.as_ref(), count, valid)
 }